@@ -0,0 +1,94 @@
+use crate::point::Point;
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+/// screen directions, `Up` decreases `y`
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// `U`, `R`, `D` or `L`
+    pub fn from_letter(c: char) -> Result<Self, String> {
+        match c {
+            'U' => Ok(Direction::Up),
+            'R' => Ok(Direction::Right),
+            'D' => Ok(Direction::Down),
+            'L' => Ok(Direction::Left),
+            _ => Err(format!("Unknown direction: {}", c)),
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn turn(self, turn: Turn) -> Self {
+        match turn {
+            Turn::Left => self.turn_left(),
+            Turn::Right => self.turn_right(),
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        self.turn_left().turn_left()
+    }
+
+    pub fn delta(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn turn_full_circle_same_direction() {
+        Direction::ALL.iter().for_each(|d| {
+            assert_eq!(d.turn_left().turn_left().turn_left().turn_left(), *d);
+            assert_eq!(d.turn(Turn::Left).turn(Turn::Right), *d);
+            assert_eq!(d.reverse().reverse(), *d);
+        });
+    }
+
+    #[test]
+    fn from_letter_unknown_error() {
+        assert_eq!(Direction::from_letter('R'), Ok(Direction::Right));
+        assert!(Direction::from_letter('T').is_err());
+    }
+}
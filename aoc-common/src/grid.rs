@@ -0,0 +1,444 @@
+use std::collections::hash_map::{self, HashMap};
+use std::iter::FromIterator;
+
+use crate::image::{Image, Rgb};
+use crate::point::Point;
+
+/// smallest rectangle containing all points as (min, max), `None` without
+/// points
+pub fn bounding_box<I>(points: I) -> Option<(Point, Point)>
+where
+    I: IntoIterator<Item = Point>,
+{
+    points.into_iter().fold(None, |bounds, p| match bounds {
+        None => Some((p, p)),
+        Some((min, max)) => Some((
+            Point::new(min.x.min(p.x), min.y.min(p.y)),
+            Point::new(max.x.max(p.x), max.y.max(p.y)),
+        )),
+    })
+}
+
+/// renders the rectangle `min..=max` row by row, rows joined by newlines
+pub fn render<F>(min: Point, max: Point, pixel: F) -> String
+where
+    F: Fn(Point) -> char,
+{
+    (min.y..=max.y)
+        .map(|y| {
+            (min.x..=max.x)
+                .map(|x| pixel(Point::new(x, y)))
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// cells addressed by `Point`, stored densely by `DenseGrid` and sparsely by
+/// `SparseGrid`
+pub trait Grid<T> {
+    fn get(&self, p: Point) -> Option<&T>;
+
+    /// smallest rectangle containing all cells as (min, max), `None` if empty
+    fn bounds(&self) -> Option<(Point, Point)>;
+
+    /// existing cells among the orthogonal neighbors of `p`
+    fn neighbors4(&self, p: Point) -> Vec<(Point, &T)> {
+        p.neighbors4()
+            .iter()
+            .filter_map(|n| self.get(*n).map(|v| (*n, v)))
+            .collect()
+    }
+
+    /// existing cells among the orthogonal and diagonal neighbors of `p`
+    fn neighbors8(&self, p: Point) -> Vec<(Point, &T)> {
+        p.neighbors8()
+            .iter()
+            .filter_map(|n| self.get(*n).map(|v| (*n, v)))
+            .collect()
+    }
+
+    /// one char per cell, rows joined by newlines, empty grids render empty
+    fn render<F>(&self, pixel: F) -> String
+    where
+        F: Fn(Option<&T>) -> char,
+    {
+        match self.bounds() {
+            Some((min, max)) => render(min, max, |p| pixel(self.get(p))),
+            None => String::new(),
+        }
+    }
+
+    /// two rows per char with the Unicode half blocks, halves the height of
+    /// the terminal output
+    fn render_half_blocks<F>(&self, on: F) -> String
+    where
+        F: Fn(Option<&T>) -> bool,
+    {
+        let (min, max) = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return String::new(),
+        };
+
+        (min.y..=max.y)
+            .step_by(2)
+            .map(|y| {
+                (min.x..=max.x)
+                    .map(|x| {
+                        let top = on(self.get(Point::new(x, y)));
+                        let bottom = y < max.y && on(self.get(Point::new(x, y + 1)));
+
+                        match (top, bottom) {
+                            (true, true) => '\u{2588}',
+                            (true, false) => '\u{2580}',
+                            (false, true) => '\u{2584}',
+                            (false, false) => ' ',
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// one pixel per cell of the bounding box
+    fn to_image<F>(&self, color: F) -> Image
+    where
+        F: Fn(Option<&T>) -> Rgb,
+    {
+        let (min, max) = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return Image::new(0, 0, Vec::new()).unwrap(),
+        };
+
+        let pixels = (min.y..=max.y)
+            .flat_map(|y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+            .map(|p| color(self.get(p)))
+            .collect();
+
+        Image::new(
+            (max.x - min.x + 1) as usize,
+            (max.y - min.y + 1) as usize,
+            pixels,
+        )
+        .unwrap()
+    }
+}
+
+/// rectangular grid with its top left cell at the origin
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct DenseGrid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> DenseGrid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        DenseGrid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> DenseGrid<T> {
+    /// cells row by row, the last row has to be complete
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Result<Self, String> {
+        if width == 0 || !cells.len().is_multiple_of(width) {
+            return Err(format!(
+                "{} cells do not fill rows of width {}",
+                cells.len(),
+                width
+            ));
+        }
+
+        Ok(DenseGrid {
+            width,
+            height: cells.len() / width,
+            cells,
+        })
+    }
+
+    /// one row per non-empty line, all rows need the same length
+    pub fn parse<F>(text: &str, cell: F) -> Result<Self, String>
+    where
+        F: Fn(char) -> Result<T, String>,
+    {
+        let mut width: Option<usize> = None;
+        let mut cells: Vec<T> = Vec::new();
+
+        for (i, line) in text.lines().enumerate().filter(|(_, l)| !l.is_empty()) {
+            let row_len = line.chars().count();
+
+            if *width.get_or_insert(row_len) != row_len {
+                return Err(format!(
+                    "line {}: expected {} cells, found {}",
+                    i + 1,
+                    width.unwrap_or_default(),
+                    row_len
+                ));
+            }
+
+            for c in line.chars() {
+                cells.push(cell(c).map_err(|e| format!("line {}: {}", i + 1, e))?);
+            }
+        }
+
+        DenseGrid::from_vec(width.ok_or_else(|| "empty grid".to_owned())?, cells)
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    fn index(&self, p: Point) -> Option<usize> {
+        if p.x < 0 || p.y < 0 || p.x as usize >= self.width || p.y as usize >= self.height {
+            return None;
+        }
+
+        Some(p.y as usize * self.width + p.x as usize)
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        let index = self.index(p)?;
+        self.cells.get_mut(index)
+    }
+
+    pub fn set(&mut self, p: Point, value: T) -> Result<(), String> {
+        let cell = self
+            .get_mut(p)
+            .ok_or_else(|| format!("{:?} is outside of the grid", p))?;
+        *cell = value;
+
+        Ok(())
+    }
+
+    /// all cells row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        let width = self.width;
+
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, v)| (Point::new((i % width) as i64, (i / width) as i64), v))
+    }
+}
+
+impl DenseGrid<char> {
+    pub fn from_ascii(text: &str) -> Result<Self, String> {
+        DenseGrid::parse(text, Ok)
+    }
+}
+
+impl<T> Grid<T> for DenseGrid<T> {
+    fn get(&self, p: Point) -> Option<&T> {
+        self.index(p).and_then(|i| self.cells.get(i))
+    }
+
+    fn bounds(&self) -> Option<(Point, Point)> {
+        if self.cells.is_empty() {
+            return None;
+        }
+
+        Some((
+            Point::ORIGIN,
+            Point::new(self.width as i64 - 1, self.height as i64 - 1),
+        ))
+    }
+}
+
+/// unbounded grid, only set cells are stored
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        SparseGrid::default()
+    }
+
+    /// one row per line starting at the origin, `cell` returns `None` for
+    /// chars that leave the cell unset
+    pub fn parse<F>(text: &str, cell: F) -> Result<Self, String>
+    where
+        F: Fn(char) -> Result<Option<T>, String>,
+    {
+        let mut grid = SparseGrid::new();
+
+        for (y, line) in text.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if let Some(v) = cell(c).map_err(|e| format!("line {}: {}", y + 1, e))? {
+                    grid.set(Point::new(x as i64, y as i64), v);
+                }
+            }
+        }
+
+        Ok(grid)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    pub fn set(&mut self, p: Point, value: T) -> Option<T> {
+        self.cells.insert(p, value)
+    }
+
+    pub fn entry(&mut self, p: Point) -> hash_map::Entry<'_, Point, T> {
+        self.cells.entry(p)
+    }
+
+    /// set cells in arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(p, v)| (*p, v))
+    }
+}
+
+impl SparseGrid<char> {
+    /// all chars except `background` become cells
+    pub fn from_ascii(text: &str, background: char) -> Self {
+        SparseGrid::parse(text, |c| Ok(Some(c).filter(|c| *c != background))).unwrap()
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        SparseGrid {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> Grid<T> for SparseGrid<T> {
+    fn get(&self, p: Point) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    fn bounds(&self) -> Option<(Point, Point)> {
+        bounding_box(self.cells.keys().copied())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bounding_box_correct_output() {
+        let points = vec![Point::new(2, -1), Point::new(-3, 4), Point::new(0, 0)];
+
+        assert_eq!(
+            bounding_box(points),
+            Some((Point::new(-3, -1), Point::new(2, 4)))
+        );
+        assert_eq!(bounding_box(Vec::new()), None);
+    }
+
+    #[test]
+    fn render_correct_output() {
+        let output = render(Point::new(0, 0), Point::new(2, 1), |p| {
+            if p.x == p.y {
+                '#'
+            } else {
+                '.'
+            }
+        });
+
+        assert_eq!(output, "#..\n.#.");
+    }
+
+    #[test]
+    fn dense_grid_parse_correct_output() {
+        let grid = DenseGrid::from_ascii("#.\n.#\n..\n").unwrap();
+
+        assert_eq!(grid.get_width(), 2);
+        assert_eq!(grid.get_height(), 3);
+        assert_eq!(grid.get(Point::new(1, 1)), Some(&'#'));
+        assert_eq!(grid.get(Point::new(2, 1)), None);
+        assert_eq!(grid.render(|c| *c.unwrap()), "#.\n.#\n..");
+    }
+
+    #[test]
+    fn dense_grid_parse_ragged_rows_error() {
+        assert_eq!(
+            DenseGrid::from_ascii("##\n#\n"),
+            Err("line 2: expected 2 cells, found 1".to_owned())
+        );
+        assert_eq!(
+            DenseGrid::parse("#x", |c| match c {
+                '#' => Ok(true),
+                _ => Err(format!("unknown cell '{}'", c)),
+            }),
+            Err("line 1: unknown cell 'x'".to_owned())
+        );
+    }
+
+    #[test]
+    fn neighbors_only_existing_cells() {
+        let grid = DenseGrid::from_vec(2, vec![1, 2, 3, 4]).unwrap();
+
+        assert_eq!(
+            grid.neighbors4(Point::ORIGIN),
+            vec![(Point::new(1, 0), &2), (Point::new(0, 1), &3)]
+        );
+        assert_eq!(grid.neighbors8(Point::ORIGIN).len(), 3);
+    }
+
+    #[test]
+    fn sparse_grid_bounds_and_render_correct_output() {
+        let grid = SparseGrid::from_ascii("..#\n#..", '.');
+
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.bounds(), Some((Point::new(0, 0), Point::new(2, 1))));
+        assert_eq!(grid.render(|c| c.map_or(' ', |_| '#')), "  #\n#  ");
+        assert_eq!(SparseGrid::<char>::new().render(|_| '#'), "");
+    }
+
+    #[test]
+    fn render_half_blocks_correct_output() {
+        let grid = SparseGrid::from_ascii("#.#\n##.\n.#.", '.');
+
+        assert_eq!(
+            grid.render_half_blocks(|c| c.is_some()),
+            "\u{2588}\u{2584}\u{2580}\n \u{2580} "
+        );
+    }
+
+    #[test]
+    fn to_image_correct_output() {
+        let grid: SparseGrid<bool> = vec![(Point::new(-1, 0), true), (Point::new(1, 0), false)]
+            .into_iter()
+            .collect();
+        let image = grid.to_image(|c| match c {
+            Some(true) => [255, 255, 255],
+            _ => [0, 0, 0],
+        });
+
+        assert_eq!(image.get_width(), 3);
+        assert_eq!(image.get_pixel(0, 0), Some([255, 255, 255]));
+        assert_eq!(image.get_pixel(1, 0), Some([0, 0, 0]));
+    }
+}
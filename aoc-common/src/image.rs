@@ -0,0 +1,395 @@
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read};
+use std::path::Path;
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+/// black through red and yellow to white for `t` from 0 to 1
+pub fn heat(t: f64) -> Rgb {
+    let channel =
+        |offset: f64| ((t.clamp(0.0, 1.0) * 3.0 - offset).clamp(0.0, 1.0) * 255.0).round() as u8;
+
+    [channel(0.0), channel(1.0), channel(2.0)]
+}
+
+/// RGB raster, pixels row by row
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, pixels: Vec<Rgb>) -> Result<Self, String> {
+        if width.checked_mul(height) != Some(pixels.len()) {
+            return Err(format!(
+                "{} pixels do not fit {}x{}",
+                pixels.len(),
+                width,
+                height
+            ));
+        }
+
+        Ok(Image {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get_pixel(&self, x: usize, y: usize) -> Option<Rgb> {
+        if x >= self.width {
+            return None;
+        }
+
+        self.pixels.get(y * self.width + x).copied()
+    }
+
+    /// every pixel becomes a `factor`x`factor` square, puzzle images are tiny
+    pub fn scale(&self, factor: usize) -> Image {
+        let pixels = (0..self.height * factor)
+            .flat_map(|y| (0..self.width * factor).map(move |x| (x / factor, y / factor)))
+            .map(|(x, y)| self.pixels[y * self.width + x])
+            .collect();
+
+        Image {
+            width: self.width * factor,
+            height: self.height * factor,
+            pixels,
+        }
+    }
+
+    /// next header token of a PBM, skipping whitespace and comments
+    fn pbm_token<'a>(data: &'a [u8], pos: &mut usize) -> Result<&'a str, String> {
+        while *pos < data.len() {
+            match data[*pos] {
+                b'#' => {
+                    while *pos < data.len() && data[*pos] != b'\n' {
+                        *pos += 1;
+                    }
+                }
+                b if b.is_ascii_whitespace() => *pos += 1,
+                _ => break,
+            }
+        }
+
+        let start = *pos;
+        while *pos < data.len() && !data[*pos].is_ascii_whitespace() {
+            *pos += 1;
+        }
+
+        match std::str::from_utf8(&data[start..*pos]) {
+            Ok(token) if !token.is_empty() => Ok(token),
+            _ => Err("truncated PBM header".to_owned()),
+        }
+    }
+
+    /// plain (P1) or raw (P4) PBM, set bits are black
+    pub fn from_pbm(data: &[u8]) -> Result<Image, String> {
+        let mut pos = 0;
+        let magic = Image::pbm_token(data, &mut pos)?;
+
+        let mut size = || {
+            Image::pbm_token(data, &mut pos)?
+                .parse::<usize>()
+                .map_err(|e| format!("PBM size: {}", e))
+        };
+        let (width, height) = (size()?, size()?);
+
+        if width.checked_mul(height).is_none() {
+            return Err(format!("PBM size {}x{} overflows", width, height));
+        }
+
+        let bits: Vec<bool> = match magic {
+            "P1" => {
+                let mut bits: Vec<bool> = Vec::new();
+                let mut comment = false;
+
+                for b in &data[pos..] {
+                    match b {
+                        b'\n' => comment = false,
+                        _ if comment || b.is_ascii_whitespace() => {}
+                        b'#' => comment = true,
+                        b'0' | b'1' => bits.push(*b == b'1'),
+                        _ => return Err(format!("unexpected PBM pixel '{}'", *b as char)),
+                    }
+                }
+
+                bits
+            }
+            "P4" => {
+                let raw = data.get(pos + 1..).unwrap_or_default();
+                let stride = width.div_ceil(8);
+
+                // can't overflow, the stride is at most the width
+                if raw.len() < stride * height {
+                    return Err(format!(
+                        "{} bytes do not fit {}x{}",
+                        raw.len(),
+                        width,
+                        height
+                    ));
+                }
+
+                (0..height)
+                    .flat_map(|y| (0..width).map(move |x| (x, y)))
+                    .map(|(x, y)| raw[y * stride + x / 8] >> (7 - x % 8) & 1 == 1)
+                    .collect()
+            }
+            _ => return Err(format!("unsupported PBM format '{}'", magic)),
+        };
+
+        Image::new(
+            width,
+            height,
+            bits.into_iter()
+                .map(|b| if b { BLACK } else { WHITE })
+                .collect(),
+        )
+    }
+
+    pub fn read_pbm(path: &Path) -> Result<Image, String> {
+        fs::read(path)
+            .map_err(|e| e.to_string())
+            .and_then(|data| Image::from_pbm(&data))
+            .map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// first frame of a PNG of any color type, alpha is ignored
+    pub fn read_png(path: &Path) -> Result<Image, String> {
+        let file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        Image::decode_png(BufReader::new(file)).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    fn decode_png<R: Read>(r: R) -> Result<Image, String> {
+        let mut decoder = png::Decoder::new(r);
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+
+        let mut reader = decoder.read_info().map_err(|e| e.to_string())?;
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf).map_err(|e| e.to_string())?;
+
+        let channels = match info.color_type {
+            png::ColorType::Grayscale => 1,
+            png::ColorType::GrayscaleAlpha => 2,
+            png::ColorType::Rgb => 3,
+            png::ColorType::Rgba => 4,
+            png::ColorType::Indexed => return Err("unexpanded palette".to_owned()),
+        };
+
+        let (width, height) = (info.width as usize, info.height as usize);
+        let line_size = info.line_size;
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| y * line_size + x * channels))
+            .map(|i| match channels {
+                1 | 2 => [buf[i]; 3],
+                _ => [buf[i], buf[i + 1], buf[i + 2]],
+            })
+            .collect();
+
+        Image::new(width, height, pixels)
+    }
+
+    /// binary PPM (P6)
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut ppm = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        ppm.extend(self.pixels.iter().flatten());
+
+        ppm
+    }
+
+    pub fn write_ppm(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.to_ppm()).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn write_png(&self, path: &Path) -> Result<(), String> {
+        let file = File::create(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        self.encode_png(BufWriter::new(file))
+            .map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// animated PNG showing every frame for `delay_ms`, all frames need the
+    /// same size
+    pub fn write_animated_png(frames: &[Image], delay_ms: u16, path: &Path) -> Result<(), String> {
+        let file = File::create(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        Image::encode_animated_png(frames, delay_ms, BufWriter::new(file))
+            .map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    fn encode_animated_png<W: std::io::Write>(
+        frames: &[Image],
+        delay_ms: u16,
+        w: W,
+    ) -> Result<(), String> {
+        let first = frames.first().ok_or_else(|| "no frames".to_owned())?;
+
+        if let Some(f) = frames
+            .iter()
+            .find(|f| (f.width, f.height) != (first.width, first.height))
+        {
+            return Err(format!(
+                "frame size {}x{} differs from {}x{}",
+                f.width, f.height, first.width, first.height
+            ));
+        }
+
+        let mut encoder = png::Encoder::new(w, first.width as u32, first.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .set_animated(frames.len() as u32, 0)
+            .and_then(|_| encoder.set_frame_delay(delay_ms, 1000))
+            .map_err(|e| e.to_string())?;
+
+        let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+
+        for frame in frames {
+            let data: Vec<u8> = frame.pixels.iter().flatten().copied().collect();
+            writer.write_image_data(&data).map_err(|e| e.to_string())?;
+        }
+
+        writer.finish().map_err(|e| e.to_string())
+    }
+
+    fn encode_png<W: std::io::Write>(&self, w: W) -> Result<(), String> {
+        let mut encoder = png::Encoder::new(w, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let data: Vec<u8> = self.pixels.iter().flatten().copied().collect();
+
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&data))
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn checkerboard() -> Image {
+        Image::new(2, 2, vec![BLACK, WHITE, WHITE, BLACK]).unwrap()
+    }
+
+    #[test]
+    fn new_wrong_size_error() {
+        assert_eq!(
+            Image::new(2, 2, vec![BLACK]),
+            Err("1 pixels do not fit 2x2".to_owned())
+        );
+    }
+
+    #[test]
+    fn to_ppm_correct_output() {
+        let mut expected = b"P6\n2 2\n255\n".to_vec();
+        expected.extend(&[0, 0, 0, 255, 255, 255, 255, 255, 255, 0, 0, 0]);
+
+        assert_eq!(checkerboard().to_ppm(), expected);
+    }
+
+    #[test]
+    fn scale_correct_output() {
+        let scaled = checkerboard().scale(2);
+
+        assert_eq!(scaled.get_width(), 4);
+        assert_eq!(scaled.get_pixel(1, 1), Some(BLACK));
+        assert_eq!(scaled.get_pixel(2, 1), Some(WHITE));
+        assert_eq!(scaled.get_pixel(4, 0), None);
+    }
+
+    #[test]
+    fn encode_png_decodes_to_same_pixels() {
+        let mut png_data: Vec<u8> = Vec::new();
+        checkerboard().encode_png(&mut png_data).unwrap();
+
+        let decoder = png::Decoder::new(png_data.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut buf = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut buf).unwrap();
+
+        assert_eq!(&buf[..6], &[0, 0, 0, 255, 255, 255]);
+    }
+
+    #[test]
+    fn decode_png_same_as_encoded() {
+        let mut png_data: Vec<u8> = Vec::new();
+        checkerboard().scale(3).encode_png(&mut png_data).unwrap();
+
+        assert_eq!(
+            Image::decode_png(png_data.as_slice()),
+            Ok(checkerboard().scale(3))
+        );
+        assert!(Image::decode_png(&b"P1"[..]).is_err());
+    }
+
+    #[test]
+    fn from_pbm_correct_output() {
+        let plain = b"P1\n# checkerboard\n2 2\n1 0\n01\n";
+        let raw = b"P4 2 2\n\x80\x40";
+
+        assert_eq!(Image::from_pbm(plain), Ok(checkerboard()));
+        assert_eq!(Image::from_pbm(raw), Ok(checkerboard()));
+        assert_eq!(
+            Image::from_pbm(b"P4 9 1\n\x80"),
+            Err("1 bytes do not fit 9x1".to_owned())
+        );
+        assert_eq!(
+            Image::from_pbm(b"P3 1 1\n1"),
+            Err("unsupported PBM format 'P3'".to_owned())
+        );
+    }
+
+    #[test]
+    fn from_pbm_malformed_header_error() {
+        assert_eq!(
+            Image::from_pbm(b"P4 99999999999 99999999999\n\x80"),
+            Err("PBM size 99999999999x99999999999 overflows".to_owned())
+        );
+        assert_eq!(
+            Image::from_pbm(b"P1 99999999999 99999999999\n1"),
+            Err("PBM size 99999999999x99999999999 overflows".to_owned())
+        );
+    }
+
+    #[test]
+    fn heat_correct_output() {
+        assert_eq!(heat(0.0), BLACK);
+        assert_eq!(heat(1.0 / 3.0), [255, 0, 0]);
+        assert_eq!(heat(2.0), WHITE);
+    }
+
+    #[test]
+    fn encode_animated_png_correct_frame_count() {
+        let frames = vec![checkerboard(), checkerboard().scale(1)];
+        let mut png_data: Vec<u8> = Vec::new();
+        Image::encode_animated_png(&frames, 100, &mut png_data).unwrap();
+
+        let reader = png::Decoder::new(png_data.as_slice()).read_info().unwrap();
+
+        assert_eq!(
+            reader.info().animation_control().map(|a| a.num_frames),
+            Some(2)
+        );
+        assert_eq!(
+            Image::encode_animated_png(&[checkerboard(), checkerboard().scale(2)], 100, Vec::new()),
+            Err("frame size 4x4 differs from 2x2".to_owned())
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// whole file, errors mention the path
+pub fn read_to_string(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// one value per non-empty line, errors mention the line number
+pub fn parse_lines<T>(text: &str) -> Result<Vec<T>, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    text.lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(i, l)| {
+            l.trim()
+                .parse::<T>()
+                .map_err(|e| format!("line {}: {} ('{}')", i + 1, e, l.trim()))
+        })
+        .collect()
+}
+
+/// values separated by `separator`, surrounding whitespace and empty tokens
+/// (e.g. after a trailing separator) are ignored
+pub fn parse_separated<T>(text: &str, separator: char) -> Result<Vec<T>, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    text.split(separator)
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .enumerate()
+        .map(|(i, t)| {
+            t.parse::<T>()
+                .map_err(|e| format!("token {}: {} ('{}')", i, e, t))
+        })
+        .collect()
+}
+
+/// all signed integers in `text` in order, everything else is ignored, e.g.
+/// `<x=-1, y=0, z=2>` yields `[-1, 0, 2]`
+pub fn integers(text: &str) -> Vec<i64> {
+    let mut integers: Vec<i64> = Vec::new();
+    let mut current = String::new();
+
+    for c in text.chars().chain(std::iter::once(' ')) {
+        if c.is_ascii_digit() || (c == '-' && current.is_empty()) {
+            current.push(c);
+            continue;
+        }
+
+        if let Ok(i) = current.parse::<i64>() {
+            integers.push(i);
+        }
+
+        current.clear();
+
+        if c == '-' {
+            current.push(c);
+        }
+    }
+
+    integers
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_lines_correct_output() {
+        assert_eq!(parse_lines::<u32>("12\n\n 14\n"), Ok(vec![12, 14]));
+    }
+
+    #[test]
+    fn parse_lines_invalid_line_error() {
+        assert_eq!(
+            parse_lines::<u32>("12\n\nx\n"),
+            Err("line 3: invalid digit found in string ('x')".to_owned())
+        );
+    }
+
+    #[test]
+    fn parse_separated_trailing_separator_correct_output() {
+        assert_eq!(
+            parse_separated::<i64>("1,-2, 3,\n", ','),
+            Ok(vec![1, -2, 3])
+        );
+    }
+
+    #[test]
+    fn integers_correct_output() {
+        assert_eq!(integers("<x=-1, y=0, z=12>"), vec![-1, 0, 12]);
+        assert_eq!(integers("a-b--3 4-5"), vec![-3, 4, -5]);
+    }
+}
@@ -0,0 +1,14 @@
+mod solution;
+
+pub mod direction;
+pub mod grid;
+pub mod image;
+pub mod input;
+pub mod ocr;
+pub mod point;
+
+pub use direction::{Direction, Turn};
+pub use grid::{DenseGrid, Grid, SparseGrid};
+pub use image::Image;
+pub use point::{Point, Point3};
+pub use solution::Solution;
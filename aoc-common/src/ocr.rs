@@ -0,0 +1,305 @@
+use std::convert::TryFrom;
+
+use crate::grid::Grid;
+use crate::point::Point;
+
+/// capital letters of one size, `pitch` columns per letter including the gap
+struct Font {
+    height: usize,
+    pitch: usize,
+    glyphs: &'static [(char, &'static str)],
+}
+
+/// 4x6 letters as printed by e.g. 2019 day 8 and day 11, `Y` is 5 wide and
+/// `I` starts with an unlit column
+const SMALL: Font = Font {
+    height: 6,
+    pitch: 5,
+    glyphs: &[
+        ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+        ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+        ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+        ('E', "####\n#...\n###.\n#...\n#...\n####"),
+        ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+        ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+        ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+        ('I', ".###\n..#.\n..#.\n..#.\n..#.\n.###"),
+        ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+        ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+        ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+        ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+        ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+        ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+        ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+        ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+        ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+        ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+    ],
+};
+
+/// 6x10 letters as printed by e.g. 2018 day 10
+const LARGE: Font = Font {
+    height: 10,
+    pitch: 8,
+    glyphs: &[
+        (
+            'A',
+            "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+        ),
+        (
+            'B',
+            "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+        ),
+        (
+            'C',
+            ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+        ),
+        (
+            'E',
+            "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+        ),
+        (
+            'F',
+            "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+        ),
+        (
+            'G',
+            ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+        ),
+        (
+            'H',
+            "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+        ),
+        (
+            'J',
+            "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+        ),
+        (
+            'K',
+            "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+        ),
+        (
+            'L',
+            "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+        ),
+        (
+            'N',
+            "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+        ),
+        (
+            'P',
+            "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+        ),
+        (
+            'R',
+            "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+        ),
+        (
+            'X',
+            "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+        ),
+        (
+            'Z',
+            "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+        ),
+    ],
+};
+
+impl Font {
+    /// most unlit columns any letter starts with
+    fn max_indent(&self) -> usize {
+        self.glyphs
+            .iter()
+            .map(|(_, pattern)| {
+                pattern
+                    .lines()
+                    .map(|row| row.find('#').unwrap_or(row.len()))
+                    .min()
+                    .unwrap_or_default()
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// letter whose pattern, padded with unlit columns to the pitch, equals
+    /// `glyph`
+    fn recognize(&self, glyph: &[Vec<bool>]) -> Option<char> {
+        self.glyphs
+            .iter()
+            .find(|(_, pattern)| {
+                pattern
+                    .lines()
+                    .zip(glyph)
+                    .all(|(pattern_row, row)| self.row_matches(pattern_row, row))
+            })
+            .map(|(c, _)| *c)
+    }
+
+    fn row_matches(&self, pattern_row: &str, row: &[bool]) -> bool {
+        let pattern_row: Vec<bool> = pattern_row.chars().map(|c| c == '#').collect();
+
+        (0..self.pitch).all(|x| pattern_row.get(x).copied().unwrap_or(false) == row[x])
+    }
+}
+
+/// text in the grid, the font is chosen by the height of the lit area and
+/// the first letter starts at its leftmost lit column
+pub fn recognize<T, G, F>(grid: &G, lit: F) -> Result<String, String>
+where
+    G: Grid<T>,
+    F: Fn(Option<&T>) -> bool,
+{
+    let (min, max) = grid
+        .bounds()
+        .ok_or_else(|| "nothing to recognize".to_owned())?;
+
+    let rows: Vec<Vec<bool>> = (min.y..=max.y)
+        .map(|y| {
+            (min.x..=max.x)
+                .map(|x| lit(grid.get(Point::new(x, y))))
+                .collect()
+        })
+        .collect();
+
+    recognize_rows(&rows)
+}
+
+/// like `recognize` for text where `#` is lit
+pub fn recognize_ascii(text: &str) -> Result<String, String> {
+    let rows: Vec<Vec<bool>> = text
+        .lines()
+        .map(|l| l.chars().map(|c| c == '#').collect())
+        .collect();
+
+    recognize_rows(&rows)
+}
+
+fn recognize_rows(rows: &[Vec<bool>]) -> Result<String, String> {
+    let lit_rows: Vec<usize> = (0..rows.len())
+        .filter(|y| rows[*y].contains(&true))
+        .collect();
+
+    let (top, bottom) = match (lit_rows.first(), lit_rows.last()) {
+        (Some(top), Some(bottom)) => (*top, *bottom),
+        _ => return Err("nothing to recognize".to_owned()),
+    };
+
+    let rows = &rows[top..=bottom];
+
+    let font = [SMALL, LARGE]
+        .iter()
+        .find(|f| f.height == rows.len())
+        .ok_or_else(|| format!("no font with height {}", rows.len()))?;
+
+    let width = rows.iter().map(Vec::len).max().unwrap_or_default() as isize;
+
+    let left = (0..width)
+        .find(|x| (0..rows.len()).any(|y| lit(rows, *x, y)))
+        .unwrap_or_default();
+
+    // the first letter may start with unlit columns, e.g. `I`
+    let results: Vec<Result<String, String>> = (0..=font.max_indent() as isize)
+        .map(|indent| recognize_from(rows, font, left - indent, width))
+        .collect();
+
+    results
+        .iter()
+        .find(|r| r.is_ok())
+        .unwrap_or(&results[0])
+        .clone()
+}
+
+fn lit(rows: &[Vec<bool>], x: isize, y: usize) -> bool {
+    usize::try_from(x)
+        .ok()
+        .and_then(|x| rows[y].get(x).copied())
+        .unwrap_or(false)
+}
+
+/// letters `font.pitch` columns apart from column `left` on
+fn recognize_from(
+    rows: &[Vec<bool>],
+    font: &Font,
+    left: isize,
+    width: isize,
+) -> Result<String, String> {
+    (left..width)
+        .step_by(font.pitch)
+        .map(|start| {
+            let glyph: Vec<Vec<bool>> = (0..rows.len())
+                .map(|y| {
+                    (start..start + font.pitch as isize)
+                        .map(|x| lit(rows, x, y))
+                        .collect()
+                })
+                .collect();
+            (start, glyph)
+        })
+        .filter(|(_, glyph)| glyph.iter().any(|row| row.contains(&true)))
+        .map(|(start, glyph)| {
+            font.recognize(&glyph)
+                .ok_or_else(|| format!("unknown letter at column {}", start))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::grid::SparseGrid;
+
+    #[test]
+    fn recognize_ascii_small_font_correct_output() {
+        let text = " ##    ## #   #####  ##  \n\
+                    #  #    # #   ##    #  # \n\
+                    #       #  # # ###  #  # \n\
+                    # ##    #   #  #    #### \n\
+                    #  # #  #   #  #    #  # \n \
+                    ###  ##    #  #### #  # ";
+
+        assert_eq!(recognize_ascii(text), Ok("GJYEA".to_owned()));
+    }
+
+    #[test]
+    fn recognize_ascii_small_font_i_correct_output() {
+        let text = concat!(
+            " ### #### #     ### \n",
+            "  #  #    #      #  \n",
+            "  #  ###  #      #  \n",
+            "  #  #    #      #  \n",
+            "  #  #    #      #  \n",
+            " ### #    ####  ### ",
+        );
+
+        assert_eq!(recognize_ascii(text), Ok("IFLI".to_owned()));
+    }
+
+    #[test]
+    fn recognize_large_font_correct_output() {
+        let text = "#....#..######..#####.\n\
+                    #....#.......#..#....#\n\
+                    #....#.......#..#....#\n\
+                    #....#......#...#....#\n\
+                    ######.....#....#####.\n\
+                    #....#....#.....#..#..\n\
+                    #....#...#......#...#.\n\
+                    #....#..#.......#...#.\n\
+                    #....#..#.......#....#\n\
+                    #....#..######..#....#\n";
+        let grid = SparseGrid::from_ascii(text, '.');
+
+        assert_eq!(recognize(&grid, |c| c.is_some()), Ok("HZR".to_owned()));
+    }
+
+    #[test]
+    fn recognize_unknown_letter_error() {
+        assert_eq!(
+            recognize_ascii(".##.\n#..#\n#..#\n####\n#..#\n#.##"),
+            Err("unknown letter at column 0".to_owned())
+        );
+        assert_eq!(
+            recognize_ascii("#\n#\n#"),
+            Err("no font with height 3".to_owned())
+        );
+    }
+}
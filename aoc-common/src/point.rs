@@ -0,0 +1,148 @@
+use std::ops::{Add, Sub};
+
+use crate::direction::Direction;
+
+/// 2D point in screen coordinates, `y` grows downwards
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Clone, Copy, Default)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub fn new(x: i64, y: i64) -> Self {
+        Point { x, y }
+    }
+
+    pub fn manhattan_distance(self, other: Point) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// neighbor in `direction`
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.delta()
+    }
+
+    /// orthogonal neighbors, clockwise starting above
+    pub fn neighbors4(self) -> [Point; 4] {
+        Direction::ALL.map(|d| self.step(d))
+    }
+
+    /// orthogonal and diagonal neighbors, clockwise starting above
+    pub fn neighbors8(self) -> [Point; 8] {
+        let [up, right, down, left] = self.neighbors4();
+
+        [
+            up,
+            up.step(Direction::Right),
+            right,
+            down.step(Direction::Right),
+            down,
+            down.step(Direction::Left),
+            left,
+            up.step(Direction::Left),
+        ]
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Self {
+        Point { x, y }
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Clone, Copy, Default)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3 { x: 0, y: 0, z: 0 };
+
+    pub fn new(x: i64, y: i64, z: i64) -> Self {
+        Point3 { x, y, z }
+    }
+
+    pub fn manhattan_distance(self, other: Point3) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    /// coordinates as array, e.g. to handle the axes independently
+    pub fn to_array(self) -> [i64; 3] {
+        [self.x, self.y, self.z]
+    }
+}
+
+impl From<(i64, i64, i64)> for Point3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, other: Point3) -> Point3 {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, other: Point3) -> Point3 {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn manhattan_distance_correct_output() {
+        assert_eq!(Point::new(3, -3).manhattan_distance(Point::ORIGIN), 6);
+        assert_eq!(Point3::new(1, -2, 3).manhattan_distance(Point3::ORIGIN), 6);
+    }
+
+    #[test]
+    fn step_correct_output() {
+        assert_eq!(Point::ORIGIN.step(Direction::Up), Point::new(0, -1));
+        assert_eq!(Point::new(2, 2).step(Direction::Left), Point::new(1, 2));
+    }
+
+    #[test]
+    fn neighbors_correct_output() {
+        assert_eq!(
+            Point::ORIGIN.neighbors4(),
+            [
+                Point::new(0, -1),
+                Point::new(1, 0),
+                Point::new(0, 1),
+                Point::new(-1, 0)
+            ]
+        );
+        assert_eq!(Point::ORIGIN.neighbors8()[1], Point::new(1, -1));
+        assert_eq!(Point::ORIGIN.neighbors8()[7], Point::new(-1, -1));
+    }
+}
@@ -0,0 +1,65 @@
+use std::path::Path;
+
+/// one day of the calendar, answers are formatted for printing and comparing
+/// with recorded ones
+pub trait Solution {
+    fn day(&self) -> u8;
+
+    fn part1(&self, input: &Path) -> Result<String, String>;
+
+    fn part2(&self, input: &Path) -> Result<String, String>;
+
+    /// number of solved parts, days with only part 1 return 1
+    fn parts(&self) -> u8 {
+        2
+    }
+
+    fn run(&self, part: u8, input: &Path) -> Result<String, String> {
+        match part {
+            1 => self.part1(input),
+            2 if self.parts() >= 2 => self.part2(input),
+            _ => Err(format!("day {} has no part {}", self.day(), part)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct OnlyPart1;
+
+    impl Solution for OnlyPart1 {
+        fn day(&self) -> u8 {
+            18
+        }
+
+        fn part1(&self, input: &Path) -> Result<String, String> {
+            Ok(input.display().to_string())
+        }
+
+        fn part2(&self, _input: &Path) -> Result<String, String> {
+            unreachable!()
+        }
+
+        fn parts(&self) -> u8 {
+            1
+        }
+    }
+
+    #[test]
+    fn run_existing_part_correct_output() {
+        assert_eq!(
+            OnlyPart1.run(1, Path::new("in.txt")),
+            Ok("in.txt".to_owned())
+        );
+    }
+
+    #[test]
+    fn run_missing_part_error() {
+        assert_eq!(
+            OnlyPart1.run(2, Path::new("in.txt")),
+            Err("day 18 has no part 2".to_owned())
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// recorded answers by (day, part), stored one per line as `day part answer`;
+/// newlines in answers (e.g. rendered images) are escaped as `\n`
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct Answers {
+    answers: BTreeMap<(u8, u8), String>,
+}
+
+fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(answer: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = answer.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                unescaped.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                unescaped.push('\\');
+                chars.next();
+            }
+            _ => unescaped.push(c),
+        }
+    }
+
+    unescaped
+}
+
+impl Answers {
+    pub fn new() -> Self {
+        Answers::default()
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut answers = Answers::new();
+
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut split = line.splitn(3, ' ');
+
+            let mut next_number = |name: &str| {
+                split
+                    .next()
+                    .ok_or_else(|| format!("line {}: missing {}", i + 1, name))?
+                    .parse::<u8>()
+                    .map_err(|e| format!("line {}: invalid {}: {}", i + 1, name, e))
+            };
+
+            let day = next_number("day")?;
+            let part = next_number("part")?;
+            let answer = split
+                .next()
+                .ok_or_else(|| format!("line {}: missing answer", i + 1))?;
+
+            answers.set(day, part, &unescape(answer));
+        }
+
+        Ok(answers)
+    }
+
+    /// a missing file is an empty set of answers
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Answers::new());
+        }
+
+        let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        Answers::parse(&text)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.to_string()).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&String> {
+        self.answers.get(&(day, part))
+    }
+
+    pub fn set(&mut self, day: u8, part: u8, answer: &str) {
+        self.answers.insert((day, part), answer.to_owned());
+    }
+}
+
+impl std::fmt::Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for ((day, part), answer) in &self.answers {
+            writeln!(f, "{} {} {}", day, part, escape(answer))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn answers_round_trip_correct_output() {
+        let mut answers = Answers::new();
+        answers.set(8, 2, " #\n# \\");
+        answers.set(1, 1, "3335787");
+
+        let text = answers.to_string();
+
+        assert_eq!(text, "1 1 3335787\n8 2  #\\n# \\\\\n");
+        assert_eq!(Answers::parse(&text), Ok(answers));
+    }
+
+    #[test]
+    fn answers_parse_invalid_day_error() {
+        assert_eq!(
+            Answers::parse("# comment\nx 1 2\n"),
+            Err("line 2: invalid day: invalid digit found in string".to_owned())
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use aoc_common::Solution;
+
+use crate::answers::Answers;
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Status {
+    Ok,
+    /// no answer recorded yet
+    New,
+    Regression {
+        expected: String,
+    },
+    Failed(String),
+}
+
+impl Status {
+    pub fn new(expected: Option<&String>, result: &Result<String, String>) -> Self {
+        match (expected, result) {
+            (_, Err(e)) => Status::Failed(e.clone()),
+            (None, Ok(_)) => Status::New,
+            (Some(expected), Ok(answer)) if expected == answer => Status::Ok,
+            (Some(expected), Ok(_)) => Status::Regression {
+                expected: expected.clone(),
+            },
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Status::Regression { .. } | Status::Failed(_))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Outcome {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub status: Status,
+    pub duration: Duration,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match &self.status {
+            Status::Ok => "ok".to_owned(),
+            Status::New => "new".to_owned(),
+            Status::Regression { expected } => format!(
+                "REGRESSION: expected {:?}, got {:?}",
+                expected,
+                self.answer.as_deref().unwrap_or_default()
+            ),
+            Status::Failed(e) => format!("FAILED: {}", e),
+        };
+
+        write!(
+            f,
+            "day {:>2} part {}  {:>10.3} ms  {}",
+            self.day,
+            self.part,
+            self.duration.as_secs_f64() * 1000.0,
+            status
+        )
+    }
+}
+
+/// runs one part and measures how long it took
+pub fn timed(
+    solution: &dyn Solution,
+    part: u8,
+    input: &Path,
+) -> (Result<String, String>, Duration) {
+    let start = Instant::now();
+    let result = solution.run(part, input);
+
+    (result, start.elapsed())
+}
+
+/// runs every part on its input and compares the answers with the recorded
+/// ones
+pub fn check<F>(solutions: &[Box<dyn Solution>], answers: &Answers, input: F) -> Vec<Outcome>
+where
+    F: Fn(u8) -> PathBuf,
+{
+    solutions
+        .iter()
+        .flat_map(|s| (1..=s.parts()).map(move |part| (s, part)))
+        .map(|(s, part)| {
+            let (result, duration) = timed(s.as_ref(), part, &input(s.day()));
+
+            Outcome {
+                day: s.day(),
+                part,
+                status: Status::new(answers.get(s.day(), part), &result),
+                answer: result.ok(),
+                duration,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Fake {
+        day: u8,
+    }
+
+    impl Solution for Fake {
+        fn day(&self) -> u8 {
+            self.day
+        }
+
+        fn part1(&self, input: &Path) -> Result<String, String> {
+            match self.day {
+                1 => Ok("42".to_owned()),
+                _ => Err(format!("{}: not found", input.display())),
+            }
+        }
+
+        fn part2(&self, _input: &Path) -> Result<String, String> {
+            Ok("7".to_owned())
+        }
+
+        fn parts(&self) -> u8 {
+            if self.day == 1 {
+                2
+            } else {
+                1
+            }
+        }
+    }
+
+    fn solutions() -> Vec<Box<dyn Solution>> {
+        vec![Box::new(Fake { day: 1 }), Box::new(Fake { day: 2 })]
+    }
+
+    #[test]
+    fn check_recorded_answers_correct_status() {
+        let mut answers = Answers::new();
+        answers.set(1, 1, "42");
+        answers.set(1, 2, "8");
+
+        let statuses: Vec<Status> = check(&solutions(), &answers, |d| PathBuf::from(d.to_string()))
+            .into_iter()
+            .map(|o| o.status)
+            .collect();
+
+        assert_eq!(
+            statuses,
+            vec![
+                Status::Ok,
+                Status::Regression {
+                    expected: "8".to_owned()
+                },
+                Status::Failed("2: not found".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn check_no_answers_new() {
+        let outcomes = check(&solutions()[..1], &Answers::new(), |_| PathBuf::new());
+
+        assert_eq!(outcomes[0].status, Status::New);
+        assert_eq!(outcomes[0].answer, Some("42".to_owned()));
+        assert!(!outcomes[0].status.is_failure());
+    }
+}
@@ -0,0 +1,84 @@
+use std::path::{Path, PathBuf};
+
+use aoc_common::Solution;
+
+pub fn all() -> Vec<Box<dyn Solution>> {
+    vec![
+        Box::new(day_01::Day01),
+        Box::new(day_02::Day02),
+        Box::new(day_03::Day03),
+        Box::new(day_04::Day04),
+        Box::new(day_05::Day05),
+        Box::new(day_06::Day06),
+        Box::new(day_08::Day08),
+        Box::new(day_09::Day09),
+        Box::new(day_10::Day10),
+        Box::new(day_11::Day11),
+        Box::new(day_12::Day12),
+        Box::new(day_14::Day14),
+        Box::new(day_16::Day16),
+        Box::new(day_18::Day18),
+    ]
+}
+
+pub fn find(day: u8) -> Result<Box<dyn Solution>, String> {
+    all()
+        .into_iter()
+        .find(|s| s.day() == day)
+        .ok_or_else(|| format!("day {} is not solved", day))
+}
+
+/// repository root, the parent of this crate
+pub fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+}
+
+/// recorded puzzle input of a day
+pub fn input_path(day: u8) -> PathBuf {
+    root()
+        .join(format!("day-{:02}", day))
+        .join("input")
+        .join("input.txt")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::answers::Answers;
+    use crate::ANSWERS_FILE;
+    use std::fs;
+
+    #[test]
+    fn answers_recorded_for_every_part() {
+        let answers = Answers::load(&root().join(ANSWERS_FILE)).unwrap();
+
+        let missing: Vec<(u8, u8)> = all()
+            .iter()
+            .flat_map(|s| (1..=s.parts()).map(move |p| (s.day(), p)))
+            .filter(|(day, part)| answers.get(*day, *part).is_none())
+            .collect();
+
+        assert_eq!(missing, Vec::new());
+    }
+
+    #[test]
+    fn all_covers_every_day_directory() {
+        let mut days: Vec<u8> = fs::read_dir(root())
+            .unwrap()
+            .filter_map(|e| {
+                e.ok()?
+                    .file_name()
+                    .to_str()?
+                    .strip_prefix("day-")?
+                    .parse()
+                    .ok()
+            })
+            .collect();
+        days.sort_unstable();
+
+        assert_eq!(all().iter().map(|s| s.day()).collect::<Vec<u8>>(), days);
+    }
+}
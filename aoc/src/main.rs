@@ -0,0 +1,230 @@
+mod answers;
+mod check;
+mod days;
+
+use std::env;
+use std::path::PathBuf;
+
+use answers::Answers;
+
+static USAGE: &str = "usage:
+    aoc run <day|all> [--part <1|2>] [--input <path>] [--time]
+    aoc check [--record] [--answers <path>]";
+
+static ANSWERS_FILE: &str = "answers.txt";
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+struct RunOptions {
+    /// `None` runs all days
+    day: Option<u8>,
+    part: Option<u8>,
+    input: Option<PathBuf>,
+    time: bool,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+struct CheckOptions {
+    record: bool,
+    answers: PathBuf,
+}
+
+fn next_value<'a, I>(args: &mut I, flag: &str) -> Result<&'a String, String>
+where
+    I: Iterator<Item = &'a String>,
+{
+    args.next()
+        .ok_or_else(|| format!("{} requires a value\n{}", flag, USAGE))
+}
+
+fn parse_run_options(args: &[String]) -> Result<RunOptions, String> {
+    let mut args = args.iter();
+
+    let day = match next_value(&mut args, "run")?.as_str() {
+        "all" => None,
+        d => Some(
+            d.parse::<u8>()
+                .map_err(|e| format!("invalid day '{}': {}", d, e))?,
+        ),
+    };
+
+    let mut options = RunOptions {
+        day,
+        part: None,
+        input: None,
+        time: false,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                let part = next_value(&mut args, arg)?;
+                options.part = Some(
+                    part.parse::<u8>()
+                        .map_err(|e| format!("invalid part '{}': {}", part, e))?,
+                );
+            }
+            "--input" => options.input = Some(PathBuf::from(next_value(&mut args, arg)?)),
+            "--time" => options.time = true,
+            _ => return Err(format!("unknown argument: {}\n{}", arg, USAGE)),
+        }
+    }
+
+    if options.day.is_none() && options.input.is_some() {
+        return Err("--input can only be used with a single day".to_owned());
+    }
+
+    Ok(options)
+}
+
+fn parse_check_options(args: &[String]) -> Result<CheckOptions, String> {
+    let mut options = CheckOptions {
+        record: false,
+        answers: days::root().join(ANSWERS_FILE),
+    };
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--record" => options.record = true,
+            "--answers" => options.answers = PathBuf::from(next_value(&mut args, arg)?),
+            _ => return Err(format!("unknown argument: {}\n{}", arg, USAGE)),
+        }
+    }
+
+    Ok(options)
+}
+
+/// prints the answers of the selected parts, multi-line answers (rendered
+/// images) start on their own line
+fn run(options: &RunOptions) -> Result<(), String> {
+    let solutions = match options.day {
+        Some(day) => vec![days::find(day)?],
+        None => days::all(),
+    };
+
+    let mut failures = 0;
+
+    for solution in &solutions {
+        let parts: Vec<u8> = match options.part {
+            Some(part) => vec![part],
+            None => (1..=solution.parts()).collect(),
+        };
+
+        let input = match &options.input {
+            Some(path) => path.clone(),
+            None => days::input_path(solution.day()),
+        };
+
+        for part in parts {
+            let (result, duration) = check::timed(solution.as_ref(), part, &input);
+
+            let mut header = format!("day {:>2} part {}", solution.day(), part);
+            if options.time {
+                header += &format!(" ({:.3} ms)", duration.as_secs_f64() * 1000.0);
+            }
+
+            match result {
+                Ok(answer) if answer.contains('\n') => println!("{}:\n{}", header, answer),
+                Ok(answer) => println!("{}: {}", header, answer),
+                Err(e) => {
+                    failures += 1;
+                    eprintln!("{}: error: {}", header, e);
+                }
+            }
+        }
+    }
+
+    if failures > 0 {
+        return Err(format!("{} parts failed", failures));
+    }
+
+    Ok(())
+}
+
+/// runs all parts against the recorded answers, `--record` stores the
+/// current answers of new and changed parts instead of failing
+fn run_check(options: &CheckOptions) -> Result<(), String> {
+    let mut answers = Answers::load(&options.answers)?;
+
+    let outcomes = check::check(&days::all(), &answers, days::input_path);
+    outcomes.iter().for_each(|o| println!("{}", o));
+
+    if options.record {
+        outcomes.iter().for_each(|o| {
+            if let Some(answer) = &o.answer {
+                answers.set(o.day, o.part, answer);
+            }
+        });
+
+        return answers.save(&options.answers);
+    }
+
+    let failures = outcomes.iter().filter(|o| o.status.is_failure()).count();
+    if failures > 0 {
+        return Err(format!("{} of {} parts failed", failures, outcomes.len()));
+    }
+
+    Ok(())
+}
+
+fn main() -> Result<(), String> {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("run") => run(&parse_run_options(&args[1..])?),
+        Some("check") => run_check(&parse_check_options(&args[1..])?),
+        _ => Err(USAGE.to_owned()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(a: &[&str]) -> Vec<String> {
+        a.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_run_options_correct_output() {
+        let options =
+            parse_run_options(&args(&["11", "--part", "2", "--input", "in.txt", "--time"]))
+                .unwrap();
+
+        assert_eq!(
+            options,
+            RunOptions {
+                day: Some(11),
+                part: Some(2),
+                input: Some(PathBuf::from("in.txt")),
+                time: true
+            }
+        );
+    }
+
+    #[test]
+    fn parse_run_options_all_with_input_error() {
+        assert_eq!(parse_run_options(&args(&["all"])).unwrap().day, None);
+        assert!(parse_run_options(&args(&["all", "--input", "in.txt"])).is_err());
+        assert!(parse_run_options(&args(&["eleven"])).is_err());
+    }
+
+    #[test]
+    fn parse_check_options_correct_output() {
+        let options = parse_check_options(&args(&["--answers", "a.txt", "--record"])).unwrap();
+
+        assert_eq!(
+            options,
+            CheckOptions {
+                record: true,
+                answers: PathBuf::from("a.txt")
+            }
+        );
+    }
+
+    #[test]
+    fn parse_check_options_missing_path_error() {
+        assert!(parse_check_options(&args(&["--answers"])).is_err());
+    }
+}
@@ -0,0 +1,201 @@
+use std::convert::TryFrom;
+
+/// masses and fuel, sums are checked and fail instead of wrapping
+pub type Mass = u64;
+
+/// recursion levels `levels` lists at most, a divisor of `1` takes one level
+/// per offset subtracted, the last one holds all deeper levels then
+pub const MAX_LEVELS: usize = 1000;
+
+/// fuel for a mass is `mass / divisor - offset`, at least `0`, with
+/// recursion the fuel needs fuel as well until none is needed
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct FuelFormula {
+    divisor: Mass,
+    offset: Mass,
+    recursive: bool,
+}
+
+impl FuelFormula {
+    pub fn new(divisor: Mass, offset: Mass, recursive: bool) -> Result<Self, String> {
+        if divisor == 0 {
+            return Err("fuel divisor is 0".to_owned());
+        }
+
+        if recursive && divisor == 1 && offset == 0 {
+            return Err("fuel for fuel would never reach 0".to_owned());
+        }
+
+        Ok(FuelFormula {
+            divisor,
+            offset,
+            recursive,
+        })
+    }
+
+    /// the puzzle's `mass / 3 - 2`
+    pub fn puzzle(recursive: bool) -> Self {
+        FuelFormula {
+            divisor: 3,
+            offset: 2,
+            recursive,
+        }
+    }
+
+    pub fn get_divisor(&self) -> Mass {
+        self.divisor
+    }
+
+    pub fn get_offset(&self) -> Mass {
+        self.offset
+    }
+
+    pub fn is_recursive(&self) -> bool {
+        self.recursive
+    }
+
+    /// fuel for `mass` without recursion, can't overflow
+    pub fn fuel_once(&self, mass: Mass) -> Mass {
+        (mass / self.divisor).saturating_sub(self.offset)
+    }
+
+    /// fuel per recursion level, the mass's own fuel first, no trailing `0`,
+    /// levels from `MAX_LEVELS` on are collapsed into the last one
+    pub fn levels(&self, mass: Mass) -> Result<Vec<Mass>, String> {
+        let mut levels: Vec<Mass> = Vec::new();
+        let mut fuel = self.fuel_once(mass);
+
+        while fuel > 0 {
+            if levels.len() == MAX_LEVELS - 1 {
+                let tail = self
+                    .fuel(fuel)
+                    .ok()
+                    .and_then(|f| f.checked_add(fuel))
+                    .ok_or_else(|| format!("fuel for mass {} overflows", mass))?;
+                levels.push(tail);
+                break;
+            }
+
+            levels.push(fuel);
+
+            if !self.recursive {
+                break;
+            }
+
+            fuel = self.fuel_once(fuel);
+        }
+
+        Ok(levels)
+    }
+
+    /// total fuel for `mass`, iterating at most 64 levels as every level at
+    /// least halves the fuel, a divisor of `1` is summed as arithmetic series
+    pub fn fuel(&self, mass: Mass) -> Result<Mass, String> {
+        let overflow = || format!("fuel for mass {} overflows", mass);
+        let first = self.fuel_once(mass);
+
+        if !self.recursive || first == 0 {
+            return Ok(first);
+        }
+
+        if self.divisor == 1 {
+            // first, first - offset, ... down to the last positive term
+            let terms = (first - 1) / self.offset + 1;
+            let last = first - (terms - 1) * self.offset;
+
+            return (first as u128 + last as u128)
+                .checked_mul(terms as u128)
+                .map(|s| s / 2)
+                .and_then(|s| Mass::try_from(s).ok())
+                .ok_or_else(overflow);
+        }
+
+        let mut total: Mass = 0;
+        let mut fuel = first;
+
+        while fuel > 0 {
+            total = total.checked_add(fuel).ok_or_else(overflow)?;
+            fuel = self.fuel_once(fuel);
+        }
+
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn levels_correct_output() {
+        assert_eq!(FuelFormula::puzzle(false).levels(1969), Ok(vec![654]));
+        assert_eq!(
+            FuelFormula::puzzle(true).levels(1969),
+            Ok(vec![654, 216, 70, 21, 5])
+        );
+        assert_eq!(FuelFormula::puzzle(true).levels(5), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn levels_collapsed_tail_correct_output() {
+        let formula = FuelFormula::new(1, 1, true).unwrap();
+        let levels = formula.levels(5000).unwrap();
+
+        assert_eq!(levels.len(), MAX_LEVELS);
+        assert_eq!(levels[..3], [4999, 4998, 4997]);
+        assert_eq!(levels[MAX_LEVELS - 1], 4000 * 4001 / 2);
+        assert_eq!(Ok(levels.iter().sum()), formula.fuel(5000));
+    }
+
+    #[test]
+    fn fuel_custom_formula_correct_output() {
+        let formula = FuelFormula::new(2, 0, true).unwrap();
+
+        assert_eq!(formula.fuel(100), Ok(50 + 25 + 12 + 6 + 3 + 1));
+        assert_eq!(FuelFormula::new(1, 1, true).unwrap().fuel(4), Ok(3 + 2 + 1));
+        assert_eq!(
+            FuelFormula::new(1, 3, true).unwrap().fuel(12),
+            Ok(9 + 6 + 3)
+        );
+        assert_eq!(
+            FuelFormula::new(1, 3, true).unwrap().fuel(11),
+            Ok(8 + 5 + 2)
+        );
+    }
+
+    #[test]
+    fn fuel_huge_mass_correct_output() {
+        let formula = FuelFormula::puzzle(true);
+        let mass = Mass::MAX;
+
+        assert_eq!(
+            formula.fuel(mass),
+            Ok(formula.levels(mass).unwrap().iter().sum())
+        );
+        assert_eq!(FuelFormula::puzzle(false).fuel(mass), Ok(Mass::MAX / 3 - 2));
+    }
+
+    #[test]
+    fn fuel_overflow_error() {
+        let formula = FuelFormula::new(1, 1, true).unwrap();
+
+        assert_eq!(formula.fuel(1 << 32), Ok((1 << 32) * ((1 << 32) - 1) / 2));
+        assert_eq!(
+            formula.fuel(Mass::MAX),
+            Err(format!("fuel for mass {} overflows", Mass::MAX))
+        );
+    }
+
+    #[test]
+    fn new_invalid_formula_error() {
+        assert_eq!(
+            FuelFormula::new(0, 2, false),
+            Err("fuel divisor is 0".to_owned())
+        );
+        assert_eq!(
+            FuelFormula::new(1, 0, true),
+            Err("fuel for fuel would never reach 0".to_owned())
+        );
+        assert!(FuelFormula::new(1, 0, false).is_ok());
+    }
+}
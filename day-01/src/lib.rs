@@ -0,0 +1,182 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use aoc_common::Solution;
+
+mod fuel;
+mod manifest;
+
+pub use fuel::{FuelFormula, Mass, MAX_LEVELS};
+pub use manifest::{FuelReport, Manifest, Module, ModuleFuel};
+
+pub static INPUT_PATH: &str = "input/input.txt";
+
+/// masses read line by line from any reader, empty lines are skipped and
+/// errors mention the line number
+pub struct Masses<R> {
+    reader: R,
+    line: String,
+    line_number: usize,
+}
+
+impl<R: BufRead> Masses<R> {
+    pub fn new(reader: R) -> Self {
+        Masses {
+            reader,
+            line: String::new(),
+            line_number: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Masses<R> {
+    type Item = Result<Mass, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line.clear();
+            self.line_number += 1;
+
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) if self.line.trim().is_empty() => continue,
+                Ok(_) => {
+                    let mass = self.line.trim();
+
+                    return Some(
+                        mass.parse::<Mass>()
+                            .map_err(|e| format!("line {}: {} ('{}')", self.line_number, e, mass)),
+                    );
+                }
+                Err(e) => return Some(Err(format!("line {}: {}", self.line_number, e))),
+            }
+        }
+    }
+}
+
+/// masses of a file, `-` streams stdin
+pub fn load_masses(path: &Path) -> Result<Masses<Box<dyn BufRead>>, String> {
+    let reader: Box<dyn BufRead> = if path == Path::new("-") {
+        Box::new(BufReader::new(io::stdin()))
+    } else {
+        let file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Box::new(BufReader::new(file))
+    };
+
+    Ok(Masses::new(reader))
+}
+
+fn calc_fuel(mass: Mass) -> Mass {
+    FuelFormula::puzzle(false).fuel_once(mass)
+}
+
+fn calc_fuel_rec(mass: Mass) -> Result<Mass, String> {
+    FuelFormula::puzzle(true).fuel(mass)
+}
+
+/// fuel without and with fuel for the fuel in one pass, an error instead of
+/// a wrapped sum on overflow
+pub fn fuel_sums<I>(masses: I) -> Result<(Mass, Mass), String>
+where
+    I: IntoIterator<Item = Result<Mass, String>>,
+{
+    let overflow = || "fuel sum overflows".to_owned();
+
+    masses
+        .into_iter()
+        .try_fold((0 as Mass, 0 as Mass), |(fuel, fuel_rec), mass| {
+            let mass = mass?;
+
+            Ok((
+                fuel.checked_add(calc_fuel(mass)).ok_or_else(overflow)?,
+                fuel_rec
+                    .checked_add(calc_fuel_rec(mass)?)
+                    .ok_or_else(overflow)?,
+            ))
+        })
+}
+
+pub fn part1(path: &Path) -> Result<Mass, String> {
+    fuel_sums(load_masses(path)?).map(|(fuel, _)| fuel)
+}
+
+pub fn part2(path: &Path) -> Result<Mass, String> {
+    fuel_sums(load_masses(path)?).map(|(_, fuel_rec)| fuel_rec)
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn part1(&self, input: &Path) -> Result<String, String> {
+        part1(input).map(|a| a.to_string())
+    }
+
+    fn part2(&self, input: &Path) -> Result<String, String> {
+        part2(input).map(|a| a.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn calc_fuel_supplied_inputs_output_correct() {
+        assert_eq!(calc_fuel(12), 2);
+        assert_eq!(calc_fuel(14), 2);
+        assert_eq!(calc_fuel(1969), 654);
+        assert_eq!(calc_fuel(100_756), 33583);
+    }
+
+    #[test]
+    fn load_masses_supplied_inputs_output_correct() {
+        let masses = load_masses(Path::new(INPUT_PATH));
+        assert!(masses.is_ok());
+        let m = masses.unwrap().collect::<Result<Vec<Mass>, String>>();
+        assert_eq!(m.map(|m| m.len()), Ok(100));
+    }
+
+    #[test]
+    fn load_masses_broken_input_output_error() {
+        let masses = load_masses(Path::new("input/input.broken.txt")).unwrap();
+        assert_eq!(
+            masses.collect::<Result<Vec<Mass>, String>>(),
+            Err("line 12: invalid digit found in string ('test')".to_owned())
+        );
+        assert!(load_masses(Path::new("input/missing.txt")).is_err());
+    }
+
+    #[test]
+    fn masses_skip_empty_lines() {
+        let masses = Masses::new("12\n\n  14 \n1969".as_bytes());
+
+        assert_eq!(fuel_sums(masses), Ok((2 + 2 + 654, 2 + 2 + 966)));
+    }
+
+    #[test]
+    fn calc_fuel_rec_supplied_inputs_output_correct() {
+        assert_eq!(calc_fuel_rec(12), Ok(2));
+        assert_eq!(calc_fuel_rec(1969), Ok(966));
+        assert_eq!(calc_fuel_rec(100_756), Ok(50_346));
+    }
+
+    #[test]
+    fn calc_fuel_huge_mass_correct_output() {
+        // wrapped to a negative i32 before the subtraction with u32 masses
+        assert_eq!(calc_fuel(9_000_000_000), 2_999_999_998);
+        assert_eq!(calc_fuel(Mass::MAX), 6_148_914_691_236_517_203);
+    }
+
+    #[test]
+    fn fuel_sums_overflow_error() {
+        assert_eq!(
+            fuel_sums(vec![Ok(Mass::MAX); 4]),
+            Err("fuel sum overflows".to_owned())
+        );
+    }
+}
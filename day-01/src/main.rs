@@ -1,85 +1,36 @@
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
-use std::path::PathBuf;
+use std::env;
+use std::path::Path;
 
-static INPUT_PATH: &str = "input/input.txt";
-
-fn load_masses(path: PathBuf) -> Result<Vec<u32>, String> {
-    let f = File::open(path).map_err(|e| e.to_string())?;
-    let buf = BufReader::new(f);
-    let lines: Vec<String> = buf
-        .lines()
-        .map(|l| l.map_err(|e| e.to_string()))
-        .collect::<Result<Vec<String>, String>>()?;
-
-    lines
-        .into_iter()
-        .filter(|l| !l.is_empty())
-        .map(|l| l.parse::<u32>())
-        .map(|m| m.map_err(|e| e.to_string()))
-        .collect::<Result<Vec<u32>, String>>()
-}
-
-fn calc_fuel(mass: u32) -> u32 {
-    0.max(((mass / 3) as i32) - 2) as u32
-}
-
-fn calc_fuel_rec(mass: u32) -> u32 {
-    let mass = 0.max(((mass / 3) as i32) - 2) as u32;
-    match mass {
-        0 => 0,
-        _ => calc_fuel_rec(mass) + mass,
-    }
-}
+use advent_of_code_2019::{fuel_sums, load_masses, FuelFormula, Manifest, Mass, INPUT_PATH};
 
+/// sums the masses of the optional first path argument, `-` for stdin, with
+/// a manifest path as second argument its fuel report is printed as well,
+/// the optional divisor, offset and `once` arguments that follow replace the
+/// puzzle's recursive formula
 fn main() -> Result<(), String> {
-    let masses = load_masses(PathBuf::from(INPUT_PATH))?;
+    let args: Vec<String> = env::args().collect();
+    let masses = args.get(1).map_or(INPUT_PATH, String::as_str);
 
-    // part 1
-    let fuel_required: Vec<u32> = masses.iter().map(|m| calc_fuel(*m)).collect();
-    let fuel_sum: u32 = fuel_required.into_iter().sum();
+    let (fuel_sum, fuel_sum_rec) = fuel_sums(load_masses(Path::new(masses))?)?;
 
     println!("Fuel sum: {}", fuel_sum);
-
-    // part 2
-    let fuel_required_rec: Vec<u32> = masses.into_iter().map(calc_fuel_rec).collect();
-    let fuel_sum_rec: u32 = fuel_required_rec.into_iter().sum();
-
     println!("Fuel sum rec: {}", fuel_sum_rec);
 
-    Ok(())
-}
+    if let Some(path) = args.get(2) {
+        let number = |i: usize, default: Mass| {
+            args.get(i).map_or(Ok(default), |a| {
+                a.parse::<Mass>().map_err(|e| format!("{}: {}", a, e))
+            })
+        };
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn calc_fuel_supplied_inputs_output_correct() {
-        assert_eq!(calc_fuel(12), 2);
-        assert_eq!(calc_fuel(14), 2);
-        assert_eq!(calc_fuel(1969), 654);
-        assert_eq!(calc_fuel(100_756), 33583);
-    }
+        let formula = FuelFormula::new(
+            number(3, 3)?,
+            number(4, 2)?,
+            args.get(5).is_none_or(|a| a != "once"),
+        )?;
 
-    #[test]
-    fn load_masses_supplied_inputs_output_correct() {
-        let masses = load_masses(PathBuf::from(INPUT_PATH));
-        assert!(masses.is_ok());
-        let m = masses.unwrap();
-        assert_eq!(m.len(), 100);
+        println!("{}", Manifest::load(Path::new(path))?.report(&formula)?);
     }
 
-    #[test]
-    fn load_masses_broken_input_output_error() {
-        let masses = load_masses(PathBuf::from("input/input.broken.txt"));
-        assert!(masses.is_err());
-    }
-
-    #[test]
-    fn calc_fuel_rec_supplied_inputs_output_correct() {
-        assert_eq!(calc_fuel_rec(12), 2);
-        assert_eq!(calc_fuel_rec(1969), 966);
-        assert_eq!(calc_fuel_rec(100_756), 50_346);
-    }
+    Ok(())
 }
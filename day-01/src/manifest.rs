@@ -0,0 +1,402 @@
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use aoc_common::input;
+
+use crate::{FuelFormula, Mass, MAX_LEVELS};
+
+/// part of the spacecraft, needed `quantity` times per parent, with its own
+/// `mass` and sub-assemblies
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Module {
+    pub name: String,
+    pub quantity: Mass,
+    pub mass: Mass,
+    pub parts: Vec<Module>,
+}
+
+impl Module {
+    /// `<name> [x<quantity>][: <mass>]` or a bare mass as in the puzzle input,
+    /// named after its line
+    fn parse(entry: &str, line: usize) -> Result<Self, String> {
+        if let Ok(mass) = entry.parse::<Mass>() {
+            return Ok(Module {
+                name: format!("line {}", line),
+                quantity: 1,
+                mass,
+                parts: Vec::new(),
+            });
+        }
+
+        let (head, mass) = match entry.rsplit_once(':') {
+            Some((head, mass)) => (
+                head.trim(),
+                mass.trim()
+                    .parse::<Mass>()
+                    .map_err(|e| format!("mass: {}", e))?,
+            ),
+            None => (entry, 0),
+        };
+
+        let (name, quantity) = match head.rsplit_once(' ') {
+            Some((name, q))
+                if q.len() > 1
+                    && q.starts_with('x')
+                    && q[1..].chars().all(|c| c.is_ascii_digit()) =>
+            {
+                (
+                    name.trim(),
+                    q[1..]
+                        .parse::<Mass>()
+                        .map_err(|e| format!("quantity: {}", e))?,
+                )
+            }
+            _ => (head, 1),
+        };
+
+        if name.is_empty() {
+            return Err("missing name".to_owned());
+        }
+
+        if quantity == 0 {
+            return Err(format!("<{}> quantity is <0>", name));
+        }
+
+        Ok(Module {
+            name: name.to_owned(),
+            quantity,
+            mass,
+            parts: Vec::new(),
+        })
+    }
+}
+
+/// fuel of one module, `count` is its quantity times the quantities of all
+/// assemblies it is part of
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct ModuleFuel {
+    /// names from the top level assembly down, separated by `/`
+    pub path: String,
+    pub count: Mass,
+    pub mass: Mass,
+    /// fuel per recursion level for a single module
+    pub levels: Vec<Mass>,
+    /// fuel for a single module
+    pub fuel: Mass,
+    /// fuel for all `count` modules
+    pub total: Mass,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct FuelReport {
+    pub modules: Vec<ModuleFuel>,
+    /// fuel of all modules per recursion level, the last of `MAX_LEVELS`
+    /// levels holds all deeper ones
+    pub levels: Vec<Mass>,
+    pub total: Mass,
+}
+
+impl FuelReport {
+    fn add(&mut self, module: ModuleFuel) -> Result<(), String> {
+        let overflow = || format!("fuel of {} overflows", module.path);
+
+        if self.levels.len() < module.levels.len() {
+            self.levels.resize(module.levels.len(), 0);
+        }
+
+        for (level, fuel) in self.levels.iter_mut().zip(&module.levels) {
+            *level = fuel
+                .checked_mul(module.count)
+                .and_then(|f| level.checked_add(f))
+                .ok_or_else(overflow)?;
+        }
+
+        self.total = self.total.checked_add(module.total).ok_or_else(overflow)?;
+        self.modules.push(module);
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for FuelReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .modules
+            .iter()
+            .map(|m| m.path.len())
+            .max()
+            .unwrap_or(0)
+            .max("module".len());
+
+        writeln!(
+            f,
+            "{:<w$} {:>6} {:>10} {:>10}",
+            "module",
+            "count",
+            "fuel",
+            "total",
+            w = width
+        )?;
+
+        for m in &self.modules {
+            writeln!(
+                f,
+                "{:<w$} {:>6} {:>10} {:>10}",
+                m.path,
+                m.count,
+                m.fuel,
+                m.total,
+                w = width
+            )?;
+        }
+
+        for (i, level) in self.levels.iter().enumerate() {
+            let deeper = if i == MAX_LEVELS - 1 { "+" } else { "" };
+            writeln!(f, "level {}{}: {}", i, deeper, level)?;
+        }
+
+        write!(f, "total: {}", self.total)
+    }
+}
+
+/// modules of a spacecraft, one per line, parts are indented below their
+/// assembly, empty lines and lines starting with `#` are ignored, e.g.
+///
+/// ```text
+/// Command Module: 12000
+/// Booster x2: 3000
+///   Thruster x4: 1969
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct Manifest {
+    modules: Vec<Module>,
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        input::read_to_string(path)?
+            .parse()
+            .map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn get_modules(&self) -> &[Module] {
+        &self.modules
+    }
+
+    /// modules indented deeper than `indent` from `entries[*i]` on become
+    /// parts of the module before them
+    fn nest(
+        entries: &[(usize, usize, Module)],
+        i: &mut usize,
+        indent: usize,
+    ) -> Result<Vec<Module>, String> {
+        let mut modules: Vec<Module> = Vec::new();
+
+        while let Some((line, entry_indent, module)) = entries.get(*i) {
+            if *entry_indent < indent {
+                break;
+            }
+
+            if *entry_indent > indent {
+                return Err(format!("line {}: unexpected indentation", line));
+            }
+
+            let mut module = module.clone();
+            *i += 1;
+
+            if let Some((_, part_indent, _)) = entries.get(*i).filter(|(_, p, _)| *p > indent) {
+                module.parts = Manifest::nest(entries, i, *part_indent)?;
+            }
+
+            modules.push(module);
+        }
+
+        Ok(modules)
+    }
+
+    fn add_fuel(
+        report: &mut FuelReport,
+        modules: &[Module],
+        parent: Option<(&str, Mass)>,
+        formula: &FuelFormula,
+    ) -> Result<(), String> {
+        for module in modules {
+            let (path, count) = match parent {
+                Some((path, count)) => (format!("{}/{}", path, module.name), count),
+                None => (module.name.clone(), 1),
+            };
+            let count = count
+                .checked_mul(module.quantity)
+                .ok_or_else(|| format!("number of {} overflows", path))?;
+
+            if module.mass > 0 || module.parts.is_empty() {
+                let fuel = formula.fuel(module.mass)?;
+
+                report.add(ModuleFuel {
+                    path: path.clone(),
+                    count,
+                    mass: module.mass,
+                    levels: formula.levels(module.mass)?,
+                    fuel,
+                    total: fuel
+                        .checked_mul(count)
+                        .ok_or_else(|| format!("fuel of {} overflows", path))?,
+                })?;
+            }
+
+            Manifest::add_fuel(report, &module.parts, Some((&path, count)), formula)?;
+        }
+
+        Ok(())
+    }
+
+    /// fuel of every module with a mass, depth first in manifest order
+    pub fn report(&self, formula: &FuelFormula) -> Result<FuelReport, String> {
+        let mut report = FuelReport::default();
+        Manifest::add_fuel(&mut report, &self.modules, None, formula)?;
+
+        Ok(report)
+    }
+}
+
+impl FromStr for Manifest {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let entries = s
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty() && !l.trim_start().starts_with('#'))
+            .map(|(i, l)| {
+                let indent = l.len() - l.trim_start().len();
+
+                Module::parse(l.trim(), i + 1)
+                    .map(|m| (i + 1, indent, m))
+                    .map_err(|e| format!("line {}: {} ('{}')", i + 1, e, l.trim()))
+            })
+            .collect::<Result<Vec<(usize, usize, Module)>, String>>()?;
+
+        let mut i = 0;
+
+        Ok(Manifest {
+            modules: Manifest::nest(&entries, &mut i, 0)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static MANIFEST: &str = "# test craft
+Command Module: 12
+Booster x2: 1969
+  Thruster x4: 14
+
+  Tank x2
+    Valve x3: 100756
+";
+
+    #[test]
+    fn parse_nested_manifest_correct_output() {
+        let manifest: Manifest = MANIFEST.parse().unwrap();
+        let modules = manifest.get_modules();
+
+        assert_eq!(modules.len(), 2);
+        assert_eq!(modules[1].quantity, 2);
+        assert_eq!(modules[1].parts.len(), 2);
+        assert_eq!(
+            modules[1].parts[1].parts[0],
+            Module {
+                name: "Valve".to_owned(),
+                quantity: 3,
+                mass: 100_756,
+                parts: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_bare_masses_correct_output() {
+        let manifest: Manifest = "12\n\n14".parse().unwrap();
+
+        assert_eq!(manifest.get_modules()[1].name, "line 3");
+        assert_eq!(manifest.get_modules()[1].mass, 14);
+    }
+
+    #[test]
+    fn parse_invalid_manifest_error() {
+        assert_eq!(
+            "Booster x0: 12".parse::<Manifest>(),
+            Err("line 1: <Booster> quantity is <0> ('Booster x0: 12')".to_owned())
+        );
+        assert_eq!(
+            "Booster: 12t".parse::<Manifest>(),
+            Err("line 1: mass: invalid digit found in string ('Booster: 12t')".to_owned())
+        );
+        assert_eq!(
+            "Booster\n    Tank: 1\n  Valve: 1".parse::<Manifest>(),
+            Err("line 3: unexpected indentation".to_owned())
+        );
+    }
+
+    #[test]
+    fn report_correct_output() {
+        let manifest: Manifest = MANIFEST.parse().unwrap();
+        let report = manifest.report(&FuelFormula::puzzle(true)).unwrap();
+
+        let paths: Vec<(&str, Mass)> = report
+            .modules
+            .iter()
+            .map(|m| (m.path.as_str(), m.count))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                ("Command Module", 1),
+                ("Booster", 2),
+                ("Booster/Thruster", 8),
+                ("Booster/Tank/Valve", 12),
+            ]
+        );
+
+        assert_eq!(report.total, 2 + 2 * 966 + 8 * 2 + 12 * 50_346);
+        assert_eq!(report.levels[0], 2 + 2 * 654 + 8 * 2 + 12 * 33_583);
+        assert_eq!(report.levels.iter().sum::<Mass>(), report.total);
+        assert!(report
+            .to_string()
+            .ends_with(&format!("total: {}", report.total)));
+    }
+
+    #[test]
+    fn report_deep_recursion_correct_output() {
+        let manifest: Manifest = "Tank x2: 5000".parse().unwrap();
+        let formula = FuelFormula::new(1, 1, true).unwrap();
+        let report = manifest.report(&formula).unwrap();
+
+        assert_eq!(report.total, 2 * formula.fuel(5000).unwrap());
+        assert_eq!(report.levels.len(), MAX_LEVELS);
+        assert_eq!(report.levels.iter().sum::<Mass>(), report.total);
+        assert!(report.to_string().contains("\nlevel 999+: 16004000\n"));
+    }
+
+    #[test]
+    fn report_overflow_error() {
+        let manifest: Manifest = "Swarm x4294967296\n  Drone x4294967296: 12"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            manifest.report(&FuelFormula::puzzle(false)),
+            Err("number of Swarm/Drone overflows".to_owned())
+        );
+
+        let manifest: Manifest = "Hull x4: 18446744073709551615".parse().unwrap();
+
+        assert_eq!(
+            manifest.report(&FuelFormula::puzzle(false)),
+            Err("fuel of Hull overflows".to_owned())
+        );
+    }
+}
@@ -0,0 +1,535 @@
+use std::fmt;
+
+use aoc_common::Point;
+
+use crate::segment::Segment;
+use crate::{touching_pairs, SegmentWire};
+
+/// a wire running through an intersection on `segment`
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Pass {
+    pub wire: usize,
+    pub segment: Segment,
+}
+
+/// points shared by all `passes`, a single point or a stretch on one line
+/// from `from` to `to`
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Intersection {
+    pub from: Point,
+    pub to: Point,
+    pub passes: Vec<Pass>,
+}
+
+impl Intersection {
+    /// the wires involved, a wire crossing itself is listed twice
+    pub fn get_wires(&self) -> Vec<usize> {
+        self.passes.iter().map(|p| p.wire).collect()
+    }
+
+    /// steps of every pass up to `from`
+    pub fn get_steps(&self) -> Vec<usize> {
+        self.passes
+            .iter()
+            .map(|p| p.segment.steps_to(self.from))
+            .collect()
+    }
+
+    /// the only points that can be nearest to the origin or reached with the
+    /// fewest steps, both change linearly along a stretch except around the
+    /// origin, which doesn't count
+    fn candidates(&self) -> Vec<Point> {
+        let nearest = Point::new(
+            0.clamp(self.from.x, self.to.x),
+            0.clamp(self.from.y, self.to.y),
+        );
+
+        let mut candidates = vec![self.from, self.to, nearest];
+
+        if self.contains(Point::ORIGIN) {
+            candidates.extend(
+                Point::ORIGIN
+                    .neighbors4()
+                    .iter()
+                    .filter(|p| self.contains(**p)),
+            );
+        }
+
+        candidates.retain(|p| *p != Point::ORIGIN);
+
+        candidates
+    }
+
+    /// manhattan distance of the point nearest to the origin
+    pub fn nearest(&self) -> Option<i64> {
+        self.candidates()
+            .iter()
+            .map(|p| p.manhattan_distance(Point::ORIGIN))
+            .min()
+    }
+
+    /// fewest combined steps of all passes to a shared point
+    pub fn fewest_steps(&self) -> Option<usize> {
+        self.candidates()
+            .iter()
+            .map(|p| self.passes.iter().map(|s| s.segment.steps_to(*p)).sum())
+            .min()
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        (self.from.x..=self.to.x).contains(&p.x) && (self.from.y..=self.to.y).contains(&p.y)
+    }
+
+    /// wire and steps up to `p` of every pass
+    fn passes_at(&self, p: Point) -> Vec<(usize, usize)> {
+        self.passes
+            .iter()
+            .map(|s| (s.wire, s.segment.steps_to(p)))
+            .collect()
+    }
+
+    fn as_segment(&self) -> Segment {
+        Segment {
+            start: self.from,
+            end: self.to,
+            steps: 0,
+        }
+    }
+}
+
+impl fmt::Display for Intersection {
+    /// `(x, y)` or `(x, y)-(x, y)`, then wire and steps up to the point or
+    /// to both ends of the stretch for every pass
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.from.x, self.from.y)?;
+
+        if self.to != self.from {
+            write!(f, "-({}, {})", self.to.x, self.to.y)?;
+        }
+
+        let passes: Vec<String> = self
+            .passes_at(self.from)
+            .iter()
+            .zip(self.passes_at(self.to))
+            .map(|((w, from), (_, to))| {
+                if *from == to {
+                    format!("wire {} after {} steps", w, from)
+                } else {
+                    format!("wire {} after {}-{} steps", w, from, to)
+                }
+            })
+            .collect();
+
+        write!(f, ": {}", passes.join(", "))
+    }
+}
+
+/// any number of wires starting at the origin
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct Circuit {
+    wires: Vec<SegmentWire>,
+}
+
+impl Circuit {
+    pub fn new(wires: Vec<SegmentWire>) -> Self {
+        Circuit { wires }
+    }
+
+    pub fn len(&self) -> usize {
+        self.wires.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.wires.is_empty()
+    }
+
+    /// points all wires of `subset` share, the origin alone doesn't count
+    pub fn intersections(&self, subset: &[usize]) -> Result<Vec<Intersection>, String> {
+        if subset.len() < 2 {
+            return Err(format!("expected at least 2 wires, found {}", subset.len()));
+        }
+
+        if let Some(w) = subset.iter().find(|w| **w >= self.wires.len()) {
+            return Err(format!("unknown wire {}", w));
+        }
+
+        if let Some(w) = subset
+            .iter()
+            .enumerate()
+            .find_map(|(i, w)| subset[..i].iter().find(|v| *v == w))
+        {
+            return Err(format!("wire {} listed twice", w));
+        }
+
+        // every segment of the first wire is shared by the first wire alone
+        let mut shared: Vec<Intersection> = self.wires[subset[0]]
+            .get_segments()
+            .iter()
+            .map(|s| {
+                let (from, to) = s.bounds();
+
+                Intersection {
+                    from,
+                    to,
+                    passes: vec![Pass {
+                        wire: subset[0],
+                        segment: *s,
+                    }],
+                }
+            })
+            .collect();
+
+        for wire in &subset[1..] {
+            shared = Circuit::intersect(&shared, *wire, self.wires[*wire].get_segments());
+        }
+
+        Ok(Circuit::tidy(shared))
+    }
+
+    /// drops the origin and points found again at a corner, where a wire
+    /// continues on its next segment with the same steps, stretches lose such
+    /// an end, sorted by the distance to the origin
+    fn tidy(shared: Vec<Intersection>) -> Vec<Intersection> {
+        // a corner found again is a single point or lies on an end of a
+        // stretch, sorting brings stretches first and then single points in
+        // the order found, every but the first of equal ends gives it up
+        let mut ends: Vec<_> = shared
+            .iter()
+            .enumerate()
+            .flat_map(|(i, s)| {
+                let single = s.from == s.to;
+                let points = if single {
+                    vec![(s.from, false)]
+                } else {
+                    vec![(s.from, false), (s.to, true)]
+                };
+
+                points
+                    .into_iter()
+                    .map(move |(p, is_to)| (p, s.passes_at(p), single, i, is_to))
+            })
+            .collect();
+        ends.sort();
+
+        // whether `from` and `to` are given up
+        let mut given_up = vec![(false, false); shared.len()];
+        for pair in ends.windows(2) {
+            if (pair[0].0, &pair[0].1) == (pair[1].0, &pair[1].1) {
+                let (_, _, _, i, is_to) = pair[1];
+
+                if is_to {
+                    given_up[i].1 = true;
+                } else {
+                    given_up[i].0 = true;
+                }
+            }
+        }
+
+        let mut tidy: Vec<Intersection> = shared
+            .into_iter()
+            .zip(given_up)
+            .filter_map(|(mut i, (from, to))| {
+                if i.from == i.to {
+                    return Some(i).filter(|_| !from);
+                }
+
+                let step = Point::new((i.to.x - i.from.x).signum(), (i.to.y - i.from.y).signum());
+
+                if from {
+                    i.from = i.from + step;
+                }
+                if to {
+                    i.to = i.to - step;
+                }
+
+                Some(i).filter(|i| i.from.x <= i.to.x && i.from.y <= i.to.y)
+            })
+            .filter(|i| i.from != Point::ORIGIN || i.to != Point::ORIGIN)
+            .collect();
+
+        tidy.sort_by_key(|i| (i.from.manhattan_distance(Point::ORIGIN), i.from, i.to));
+
+        tidy
+    }
+
+    /// parts of `shared` the segments of `wire` run through as well
+    fn intersect(shared: &[Intersection], wire: usize, segments: &[Segment]) -> Vec<Intersection> {
+        let n = shared.len();
+        let all: Vec<Segment> = shared
+            .iter()
+            .map(Intersection::as_segment)
+            .chain(segments.iter().copied())
+            .collect();
+
+        touching_pairs(&all)
+            .into_iter()
+            .filter(|(i, j)| *i < n && *j >= n)
+            .filter_map(|(i, j)| {
+                let (from, to) = all[i].shared(&all[j])?;
+                let mut passes = shared[i].passes.clone();
+                passes.push(Pass {
+                    wire,
+                    segment: all[j],
+                });
+
+                Some(Intersection { from, to, passes })
+            })
+            .collect()
+    }
+
+    /// intersections of every pair of wires
+    pub fn pair_intersections(&self) -> Result<Vec<Intersection>, String> {
+        self.subset_intersections(2)
+    }
+
+    /// intersections of every subset of `k` wires
+    pub fn subset_intersections(&self, k: usize) -> Result<Vec<Intersection>, String> {
+        if k < 2 || k > self.wires.len() {
+            return Err(format!(
+                "expected 2 to {} wires per subset, found {}",
+                self.wires.len(),
+                k
+            ));
+        }
+
+        let shared = subsets(self.wires.len(), k)
+            .iter()
+            .map(|s| self.intersections(s))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(shared.into_iter().flatten().collect())
+    }
+
+    /// points `wire` runs through more than once, consecutive segments
+    /// don't count for the corner they share
+    pub fn self_intersections(&self, wire: usize) -> Result<Vec<Intersection>, String> {
+        let segments = self
+            .wires
+            .get(wire)
+            .ok_or_else(|| format!("unknown wire {}", wire))?
+            .get_segments();
+
+        let shared: Vec<Intersection> = touching_pairs(segments)
+            .into_iter()
+            .filter_map(|(i, j)| {
+                let (mut from, mut to) = segments[i].shared(&segments[j])?;
+
+                if j == i + 1 {
+                    // a u-turn runs back over the earlier segment up to the
+                    // corner
+                    let corner = segments[i].end;
+                    let towards = |a: i64, b: i64| a + (b - a).signum();
+
+                    if from == to {
+                        return None;
+                    } else if from == corner {
+                        from = Point::new(towards(from.x, to.x), towards(from.y, to.y));
+                    } else {
+                        to = Point::new(towards(to.x, from.x), towards(to.y, from.y));
+                    }
+                }
+
+                let passes = [segments[i], segments[j]]
+                    .iter()
+                    .map(|s| Pass { wire, segment: *s })
+                    .collect();
+
+                Some(Intersection { from, to, passes })
+            })
+            .collect();
+
+        Ok(Circuit::tidy(shared))
+    }
+}
+
+/// all sorted `k` element subsets of `0..n`
+fn subsets(n: usize, k: usize) -> Vec<Vec<usize>> {
+    if k == 0 {
+        return vec![Vec::new()];
+    }
+
+    (k - 1..n)
+        .flat_map(|last| {
+            subsets(last, k - 1).into_iter().map(move |mut s| {
+                s.push(last);
+                s
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::segment::test::wire;
+
+    fn circuit(wires: &[&str]) -> Circuit {
+        Circuit::new(wires.iter().map(|w| wire(w)).collect())
+    }
+
+    #[test]
+    fn intersections_three_wires_correct_output() {
+        let circuit = circuit(&["R8,U5,L5,D3", "U7,R6,D4,L4", "U3,R9"]);
+
+        let all = circuit.intersections(&[0, 1, 2]).unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(
+            all[0].to_string(),
+            "(3, -3): wire 0 after 20 steps, wire 1 after 20 steps, wire 2 after 6 steps"
+        );
+
+        let pairs: Vec<String> = circuit
+            .pair_intersections()
+            .unwrap()
+            .iter()
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                "(3, -3): wire 0 after 20 steps, wire 1 after 20 steps",
+                "(6, -5): wire 0 after 15 steps, wire 1 after 15 steps",
+                "(3, -3): wire 0 after 20 steps, wire 2 after 6 steps",
+                "(8, -3): wire 0 after 11 steps, wire 2 after 11 steps",
+                "(0, -3)-(0, 0): wire 1 after 3-0 steps, wire 2 after 3-0 steps",
+                "(2, -3)-(6, -3): wire 1 after 21-17 steps, wire 2 after 5-9 steps",
+            ]
+        );
+        assert_eq!(
+            circuit
+                .intersections(&[0, 1])
+                .unwrap()
+                .iter()
+                .filter_map(Intersection::fewest_steps)
+                .min(),
+            Some(30)
+        );
+    }
+
+    #[test]
+    fn intersections_collinear_correct_output() {
+        let circuit = circuit(&["R1000000,U2", "U1,R3,D1,R999999"]);
+        let shared = circuit.intersections(&[0, 1]).unwrap();
+
+        // the corners at both ends of the stretch aren't listed again
+        assert_eq!(
+            shared.iter().map(|i| i.to_string()).collect::<Vec<_>>(),
+            vec!["(3, 0)-(1000000, 0): wire 0 after 3-1000000 steps, wire 1 after 5-1000002 steps"]
+        );
+        assert_eq!(
+            shared.iter().filter_map(Intersection::nearest).min(),
+            Some(3)
+        );
+        assert_eq!(
+            shared.iter().filter_map(Intersection::fewest_steps).min(),
+            Some(3 + 3 + 2)
+        );
+    }
+
+    #[test]
+    fn intersections_only_at_origin_none() {
+        let nearest = |wires: &[&str]| {
+            circuit(wires)
+                .intersections(&[0, 1])
+                .unwrap()
+                .iter()
+                .filter_map(Intersection::nearest)
+                .min()
+        };
+
+        assert_eq!(nearest(&["R5,L10", "U5,D5"]), None);
+        assert_eq!(nearest(&["R5,L5", "R5"]), Some(1));
+    }
+
+    #[test]
+    fn intersections_invalid_subset_error() {
+        let circuit = circuit(&["R8", "U7"]);
+
+        assert_eq!(
+            circuit.intersections(&[0]),
+            Err("expected at least 2 wires, found 1".to_owned())
+        );
+        assert_eq!(
+            circuit.intersections(&[0, 2]),
+            Err("unknown wire 2".to_owned())
+        );
+        assert_eq!(
+            circuit.intersections(&[1, 1]),
+            Err("wire 1 listed twice".to_owned())
+        );
+    }
+
+    #[test]
+    fn subset_intersections_invalid_size_error() {
+        let circuit = circuit(&["R8", "U7"]);
+
+        assert_eq!(
+            circuit.subset_intersections(1),
+            Err("expected 2 to 2 wires per subset, found 1".to_owned())
+        );
+        assert_eq!(
+            circuit.subset_intersections(3),
+            Err("expected 2 to 2 wires per subset, found 3".to_owned())
+        );
+        assert_eq!(circuit.subset_intersections(2), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn self_intersections_correct_output() {
+        let circuit = circuit(&["R4,U2,L2,D4,R5,L3"]);
+        let shared = circuit.self_intersections(0).unwrap();
+
+        let stretches: Vec<(Point, Point, Vec<usize>)> = shared
+            .iter()
+            .map(|i| (i.from, i.to, i.get_steps()))
+            .collect();
+
+        assert_eq!(
+            stretches,
+            vec![
+                (Point::new(2, 0), Point::new(2, 0), vec![2, 10]),
+                (Point::new(4, 2), Point::new(6, 2), vec![14, 20]),
+            ]
+        );
+    }
+
+    #[test]
+    fn self_intersections_overlapping_stretches_corner_once() {
+        let report = |w: &str| -> Vec<String> {
+            circuit(&[w])
+                .self_intersections(0)
+                .unwrap()
+                .iter()
+                .map(|i| i.to_string())
+                .collect()
+        };
+
+        // (3, 0) is visited after 3 and 11 steps, both stretches run
+        // through it
+        assert_eq!(
+            report("R3,L4,R4"),
+            vec![
+                "(0, 0)-(2, 0): wire 0 after 0-2 steps, wire 0 after 6-4 steps",
+                "(0, 0)-(2, 0): wire 0 after 0-2 steps, wire 0 after 8-10 steps",
+                "(0, 0)-(3, 0): wire 0 after 6-3 steps, wire 0 after 8-11 steps",
+            ]
+        );
+        assert_eq!(
+            report("L2,D4,U2,D1,R4"),
+            vec![
+                "(-2, 2)-(-2, 3): wire 0 after 4-5 steps, wire 0 after 8-9 steps",
+                "(-2, 3): wire 0 after 7 steps, wire 0 after 9 steps",
+                "(-2, 3): wire 0 after 5 steps, wire 0 after 7 steps",
+            ]
+        );
+    }
+
+    #[test]
+    fn subsets_correct_output() {
+        assert_eq!(
+            subsets(4, 3),
+            vec![vec![0, 1, 2], vec![0, 1, 3], vec![0, 2, 3], vec![1, 2, 3]]
+        );
+        assert_eq!(subsets(2, 3), Vec::<Vec<usize>>::new());
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use aoc_common::{input, Direction, Point, Solution};
+
+mod circuit;
+mod segment;
+
+pub use circuit::{Circuit, Intersection, Pass};
+pub use segment::{touching_pairs, Segment, SegmentWire};
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Command {
+    direction: Direction,
+    steps: usize,
+}
+
+impl Command {
+    fn from_str(s: &str) -> Result<Command, String> {
+        let direction_raw = s
+            .chars()
+            .nth(0)
+            .ok_or_else(|| format!("direction char not found: {}", s).to_owned())?;
+
+        let direction = Direction::from_letter(direction_raw)
+            .map_err(|_| format!("Unknown direction '{}' for '{}'", direction_raw, s))?;
+
+        let steps = s[1..].parse::<usize>().map_err(|e| {
+            format!("error parsing steps <{}> for <{}>: {}", &s[1..], s, e).to_owned()
+        })?;
+
+        Ok(Command { direction, steps })
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Wire {
+    head: Point,
+    moves: usize,
+    pos_moves: HashMap<Point, usize>,
+    visited: HashSet<Point>,
+}
+
+impl Wire {
+    fn new() -> Self {
+        Wire {
+            head: Point::ORIGIN,
+            moves: 0,
+            pos_moves: HashMap::new(),
+            visited: HashSet::new(),
+        }
+    }
+    fn from_commands(cs: Vec<Command>) -> Self {
+        let mut wire = Wire::new();
+
+        cs.into_iter().for_each(|c| wire.move_head(c));
+
+        wire
+    }
+    fn move_head(&mut self, c: Command) {
+        for _ in 0..c.steps {
+            self.moves += 1;
+            let n = self.head.step(c.direction);
+            if !self.visited.contains(&n) {
+                let v = n;
+                self.visited.insert(v);
+                self.pos_moves.insert(v, self.moves);
+            }
+
+            self.head = n;
+        }
+    }
+    fn get_visited(&self) -> Vec<&Point> {
+        self.visited.iter().collect::<Vec<_>>()
+    }
+}
+
+/// wires as sets of visited cells, simple but its size grows with the
+/// length of the wires, see `SegmentWire`
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct Grid {
+    map: HashMap<Point, usize>,
+    wires: Vec<Wire>,
+}
+
+impl Grid {
+    pub fn new() -> Self {
+        Grid {
+            map: HashMap::<Point, usize>::new(),
+            wires: Vec::new(),
+        }
+    }
+
+    pub fn add_wire(&mut self, wire: Wire) {
+        wire.get_visited()
+            .iter()
+            .for_each(|p| *self.map.entry(**p).or_insert(0) += 1);
+        self.wires.push(wire);
+    }
+
+    pub fn get_intersections(&self) -> Vec<&Point> {
+        let min_visited = self.wires.len() - 1;
+
+        self.map
+            .iter()
+            .filter(|(_, v)| **v > min_visited)
+            .map(|(p, _)| p)
+            .collect()
+    }
+
+    pub fn get_nearest_intersection(&self) -> Option<i64> {
+        let intersections = self.get_intersections();
+        if intersections.is_empty() {
+            return None;
+        }
+
+        let distances: Vec<i64> = intersections
+            .iter()
+            .map(|p| p.manhattan_distance(Point::ORIGIN))
+            .collect();
+
+        let min_distance = distances.iter().fold(i64::MAX, |a, &b| a.min(b));
+
+        Some(min_distance)
+    }
+
+    pub fn get_nearest_intersection_moves(&self) -> Option<usize> {
+        let intersections = self.get_intersections();
+        if intersections.is_empty() {
+            return None;
+        }
+
+        let distances: Option<Vec<usize>> = intersections
+            .iter()
+            .map(|p| self.wires.iter().map(|w| w.pos_moves.get(p)).sum())
+            .collect();
+
+        distances.as_ref()?;
+
+        let min_distance = distances.unwrap().iter().fold(usize::MAX, |a, &b| a.min(b));
+
+        Some(min_distance)
+    }
+}
+
+fn load_commands(path: PathBuf) -> Result<Vec<Vec<Command>>, String> {
+    let commands_raw = input::read_to_string(&path)?
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(|l| l.split_terminator(',').map(|m| m.to_owned()).collect())
+        .collect::<Vec<Vec<String>>>();
+
+    let commands = commands_raw
+        .into_iter()
+        .enumerate()
+        .map(|(wn, cs)| {
+            cs.iter()
+                .enumerate()
+                .map(|(cn, c)| (cn, Command::from_str(c)))
+                .map(|(cn, m)| m.map_err(|e| format!("W#{} C#{}: {}", wn, cn, e)))
+                .collect::<Result<Vec<_>, String>>()
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(commands)
+}
+
+pub static INPUT_PATH: &str = "input/input.txt";
+
+pub fn load_grid(path: &Path) -> Result<Grid, String> {
+    let commands = load_commands(path.to_path_buf())?;
+
+    let mut grid = Grid::new();
+    commands
+        .into_iter()
+        .map(Wire::from_commands)
+        .for_each(|w| grid.add_wire(w));
+
+    Ok(grid)
+}
+
+pub fn load_wires(path: &Path) -> Result<Vec<SegmentWire>, String> {
+    Ok(load_commands(path.to_path_buf())?
+        .iter()
+        .map(|cs| SegmentWire::from_commands(cs))
+        .collect())
+}
+
+pub fn load_circuit(path: &Path) -> Result<Circuit, String> {
+    load_wires(path).map(Circuit::new)
+}
+
+/// intersections of all wires, like the `Grid`
+fn load_intersections(path: &Path) -> Result<Vec<Intersection>, String> {
+    let circuit = load_circuit(path)?;
+    let wires: Vec<usize> = (0..circuit.len()).collect();
+
+    circuit.intersections(&wires)
+}
+
+pub fn part1(path: &Path) -> Result<i64, String> {
+    load_intersections(path)?
+        .iter()
+        .filter_map(Intersection::nearest)
+        .min()
+        .ok_or_else(|| "wires do not intersect".to_owned())
+}
+
+pub fn part2(path: &Path) -> Result<usize, String> {
+    load_intersections(path)?
+        .iter()
+        .filter_map(Intersection::fewest_steps)
+        .min()
+        .ok_or_else(|| "wires do not intersect".to_owned())
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn part1(&self, input: &Path) -> Result<String, String> {
+        part1(input).map(|a| a.to_string())
+    }
+
+    fn part2(&self, input: &Path) -> Result<String, String> {
+        part2(input).map(|a| a.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_command_valid_input_valid_command() {
+        let input = "R123";
+        let command_parsed_result = Command::from_str(input);
+
+        assert!(command_parsed_result.is_ok());
+
+        let command_parsed = command_parsed_result.unwrap();
+
+        let command_expected = Command {
+            direction: Direction::Right,
+            steps: 123,
+        };
+        assert_eq!(command_parsed, command_expected);
+    }
+
+    #[test]
+    fn parse_command_invalid_input_error() {
+        let input = "T123";
+        let command_parsed_result = Command::from_str(input);
+
+        assert!(command_parsed_result.is_err());
+
+        let command_parsed_error = command_parsed_result.err().unwrap();
+
+        assert_eq!(command_parsed_error, "Unknown direction 'T' for 'T123'");
+    }
+
+    #[test]
+    fn segment_wires_same_as_grid() {
+        let expected = [("input/test1.txt", 159, 610), ("input/test2.txt", 135, 410)];
+
+        for (path, distance, steps) in expected.iter() {
+            let path = Path::new(path);
+            let grid = load_grid(path).unwrap();
+
+            assert_eq!(grid.get_nearest_intersection(), Some(*distance));
+            assert_eq!(grid.get_nearest_intersection_moves(), Some(*steps));
+            assert_eq!(part1(path), Ok(*distance));
+            assert_eq!(part2(path), Ok(*steps));
+        }
+    }
+
+    #[test]
+    fn part1_three_wires_same_as_grid() {
+        let path = Path::new("input/test3.txt");
+        let grid = load_grid(path).unwrap();
+
+        assert_eq!(part1(path), Ok(grid.get_nearest_intersection().unwrap()));
+        assert_eq!(
+            part2(path),
+            Ok(grid.get_nearest_intersection_moves().unwrap())
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::BTreeSet;
+
+use aoc_common::Point;
+
+use crate::Command;
+
+/// straight part of a wire from `start` to `end`, `steps` is the length of
+/// the wire before `start`
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+    pub steps: usize,
+}
+
+impl Segment {
+    pub fn is_horizontal(&self) -> bool {
+        self.start.y == self.end.y
+    }
+
+    /// corners (min, max), the segment is a degenerate rectangle
+    pub fn bounds(&self) -> (Point, Point) {
+        (
+            Point::new(self.start.x.min(self.end.x), self.start.y.min(self.end.y)),
+            Point::new(self.start.x.max(self.end.x), self.start.y.max(self.end.y)),
+        )
+    }
+
+    /// length of the wire up to `p` on this segment
+    pub fn steps_to(&self, p: Point) -> usize {
+        self.steps + self.start.manhattan_distance(p) as usize
+    }
+
+    /// first and last point both segments share, they are the same unless
+    /// the segments overlap on one line
+    pub fn shared(&self, other: &Segment) -> Option<(Point, Point)> {
+        let (min1, max1) = self.bounds();
+        let (min2, max2) = other.bounds();
+
+        let from = Point::new(min1.x.max(min2.x), min1.y.max(min2.y));
+        let to = Point::new(max1.x.min(max2.x), max1.y.min(max2.y));
+
+        if from.x > to.x || from.y > to.y {
+            return None;
+        }
+
+        Some((from, to))
+    }
+}
+
+/// pairs `(i, j)` with `i < j` of segments sharing at least one point,
+/// crossing segments are found by a sweep line over x, overlapping ones by
+/// sorting them per line
+pub fn touching_pairs(segments: &[Segment]) -> Vec<(usize, usize)> {
+    const INSERT: u8 = 0;
+    const QUERY: u8 = 1;
+    const REMOVE: u8 = 2;
+
+    let mut events: Vec<(i64, u8, usize)> = Vec::new();
+
+    for (i, s) in segments.iter().enumerate() {
+        let (min, max) = s.bounds();
+
+        if s.is_horizontal() {
+            events.push((min.x, INSERT, i));
+            events.push((max.x, REMOVE, i));
+        } else {
+            events.push((min.x, QUERY, i));
+        }
+    }
+
+    // horizontal segments ending at x still cross vertical ones at x
+    events.sort_unstable();
+
+    let mut pairs: Vec<(usize, usize)> = Vec::new();
+    let mut active: BTreeSet<(i64, usize)> = BTreeSet::new();
+
+    for (_, event, i) in events {
+        let (min, max) = segments[i].bounds();
+
+        match event {
+            INSERT => {
+                active.insert((min.y, i));
+            }
+            QUERY => pairs.extend(
+                active
+                    .range((min.y, 0)..=(max.y, usize::MAX))
+                    .map(|(_, h)| (i.min(*h), i.max(*h))),
+            ),
+            _ => {
+                active.remove(&(min.y, i));
+            }
+        }
+    }
+
+    collinear_pairs(segments, true, &mut pairs);
+    collinear_pairs(segments, false, &mut pairs);
+
+    pairs
+}
+
+/// overlapping horizontal or vertical segments on the same line
+fn collinear_pairs(segments: &[Segment], horizontal: bool, pairs: &mut Vec<(usize, usize)>) {
+    // line, first and last coordinate along it
+    let mut lines: Vec<(i64, i64, i64, usize)> = segments
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.is_horizontal() == horizontal)
+        .map(|(i, s)| {
+            let (min, max) = s.bounds();
+
+            if horizontal {
+                (min.y, min.x, max.x, i)
+            } else {
+                (min.x, min.y, max.y, i)
+            }
+        })
+        .collect();
+
+    lines.sort_unstable();
+
+    let mut active: Vec<(i64, i64, usize)> = Vec::new();
+
+    for (line, from, to, i) in lines {
+        active.retain(|(l, t, _)| *l == line && *t >= from);
+        pairs.extend(active.iter().map(|(_, _, j)| (i.min(*j), i.max(*j))));
+        active.push((line, to, i));
+    }
+}
+
+/// wire stored as straight segments, its size doesn't depend on how far it
+/// runs
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct SegmentWire {
+    segments: Vec<Segment>,
+}
+
+impl SegmentWire {
+    pub fn from_commands(cs: &[Command]) -> Self {
+        let mut segments: Vec<Segment> = Vec::new();
+        let mut start = Point::ORIGIN;
+        let mut steps = 0;
+
+        for c in cs.iter().filter(|c| c.steps > 0) {
+            let delta = c.direction.delta();
+            let n = c.steps as i64;
+            let end = start + Point::new(delta.x * n, delta.y * n);
+
+            segments.push(Segment { start, end, steps });
+
+            start = end;
+            steps += c.steps;
+        }
+
+        SegmentWire { segments }
+    }
+
+    pub fn get_segments(&self) -> &[Segment] {
+        &self.segments
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+
+    /// wire of comma separated commands, e.g. `R8,U5`
+    pub(crate) fn wire(commands: &str) -> SegmentWire {
+        let commands: Vec<Command> = commands
+            .split(',')
+            .map(|c| Command::from_str(c).unwrap())
+            .collect();
+
+        SegmentWire::from_commands(&commands)
+    }
+
+    #[test]
+    fn touching_pairs_correct_output() {
+        let segments = wire("R8,U5,L5,D3").get_segments().to_vec();
+        let mut pairs = touching_pairs(&segments);
+        pairs.sort_unstable();
+
+        assert_eq!(pairs, vec![(0, 1), (1, 2), (2, 3)]);
+    }
+}
@@ -1,3 +1,9 @@
+use std::path::Path;
+
+use aoc_common::{input, Solution};
+
+pub static INPUT_PATH: &str = "input/input.txt";
+
 #[allow(dead_code)]
 #[inline]
 pub fn number_to_digits(i: usize) -> Vec<u8> {
@@ -34,7 +40,7 @@ pub fn verify_password(p: usize) -> Result<usize, String> {
     }
 
     let mut same_same = false;
-    let mut last_digit = std::u8::MIN;
+    let mut last_digit = u8::MIN;
 
     for d in digits {
         if d < last_digit {
@@ -64,7 +70,7 @@ pub fn verify_password_2(p: usize) -> Result<usize, String> {
 
     let mut double = false;
     let mut digit_count: usize = 0;
-    let mut last_digit = std::u8::MIN;
+    let mut last_digit = u8::MIN;
 
     for d in digits {
         if d < last_digit {
@@ -94,6 +100,49 @@ pub fn verify_password_2(p: usize) -> Result<usize, String> {
     Ok(p)
 }
 
+/// puzzle input is the password range as `start-end`
+fn load_range(path: &Path) -> Result<(usize, usize), String> {
+    let input = input::read_to_string(path)?;
+
+    let (start, end) = input
+        .trim()
+        .split_once('-')
+        .ok_or_else(|| format!("invalid range: {}", input.trim()))?;
+
+    let start = start.parse::<usize>().map_err(|e| e.to_string())?;
+    let end = end.parse::<usize>().map_err(|e| e.to_string())?;
+
+    Ok((start, end))
+}
+
+pub fn part1(path: &Path) -> Result<usize, String> {
+    let (start, end) = load_range(path)?;
+
+    Ok((start..end).filter(|p| verify_password(*p).is_ok()).count())
+}
+
+pub fn part2(path: &Path) -> Result<usize, String> {
+    let (start, end) = load_range(path)?;
+
+    Ok((start..end).filter(|p| verify_password_2(*p).is_ok()).count())
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn part1(&self, input: &Path) -> Result<String, String> {
+        part1(input).map(|a| a.to_string())
+    }
+
+    fn part2(&self, input: &Path) -> Result<String, String> {
+        part2(input).map(|a| a.to_string())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
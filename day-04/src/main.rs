@@ -1,22 +1,15 @@
-mod lib;
+use std::path::Path;
 
-use lib::{verify_password, verify_password_2};
+use day_04::{part1, part2, INPUT_PATH};
 
-const START: usize = 136_818;
-const END: usize = 685_979;
-
-fn main() {
-    let valid_passwords: usize = (START..END)
-        .map(verify_password)
-        .filter_map(Result::ok)
-        .count();
+fn main() -> Result<(), String> {
+    let valid_passwords = part1(Path::new(INPUT_PATH))?;
 
     println!("Valid passwords 1): {}", valid_passwords);
 
-    let valid_passwords_2: usize = (START..END)
-        .map(verify_password_2)
-        .filter_map(Result::ok)
-        .count();
+    let valid_passwords_2 = part2(Path::new(INPUT_PATH))?;
+
+    println!("Valid passwords 2): {}", valid_passwords_2);
 
-    println!("Valid passwords 2): {}", valid_passwords_2)
+    Ok(())
 }
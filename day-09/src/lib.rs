@@ -1,6 +1,8 @@
+#![allow(dead_code)]
 use std::convert::TryFrom;
-use std::fs;
-use std::path::PathBuf;
+use std::path::Path;
+
+use aoc_common::{input, Solution};
 
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum Mode {
@@ -58,11 +60,11 @@ impl OpCode {
             0 => Mode::Position,
             1 => Mode::Immediate,
             2 => Mode::Relative,
-            _ => panic!(format!("unknown mode: {}", v)),
+            _ => panic!("unknown mode: {}", v),
         }
     }
 
-    pub fn from_i64(v: i64) -> OpCode {
+    fn from_i64(v: i64) -> OpCode {
         let code = v % 100;
 
         let p1_mode = OpCode::get_mode((v / 100) % 10);
@@ -107,8 +109,7 @@ pub struct IntCode {
     steps: usize,
     ip: usize,
     rb: isize,
-    single_input_value: Option<i64>,
-    inputs: Vec<i64>,
+    input_value: i64,
     done: bool,
     outputs: Vec<i64>,
     op_codes: Vec<OpCode>,
@@ -117,14 +118,13 @@ pub struct IntCode {
 }
 
 impl IntCode {
-    pub fn new(input_state: Vec<i64>, single_input_value: Option<i64>) -> Self {
+    pub fn new(input_state: Vec<i64>, input_value: i64) -> Self {
         IntCode {
             state: input_state,
             steps: 0,
             ip: 0,
             rb: 0,
-            single_input_value,
-            inputs: Vec::new(),
+            input_value,
             done: false,
             outputs: Vec::new(),
             op_codes: Vec::new(),
@@ -133,54 +133,17 @@ impl IntCode {
         }
     }
 
-    pub fn load(path: &PathBuf, single_input_value: Option<i64>) -> Result<Self, String> {
+    pub fn load(path: &Path, input_value: i64) -> Result<Self, String> {
         let input = IntCode::load_input(path)?;
 
-        Ok(IntCode::new(input, single_input_value))
+        Ok(IntCode::new(input, input_value))
     }
 
-    fn load_input(path: &PathBuf) -> Result<Vec<i64>, String> {
-        let input_raw = fs::read_to_string(path).map_err(|e| e.to_string())?;
-        let input_raw_split: Vec<&str> = input_raw.split_terminator(',').collect();
-
-        input_raw_split
-            .into_iter()
-            .map(|l| l.trim())
-            .filter(|l| !l.is_empty())
-            .map(|l| l.parse::<i64>())
-            .map(|m| m.map_err(|e| e.to_string()))
-            .collect::<Result<Vec<i64>, String>>()
+    fn load_input(path: &Path) -> Result<Vec<i64>, String> {
+        input::parse_separated(&input::read_to_string(path)?, ',')
     }
 
-    pub fn run_for_two_outputs(&mut self, input: i64) -> Result<Option<(i64, i64)>, String> {
-        if self.done {
-            return Err("run_for_two_outputs called after int code is already done".to_owned());
-        }
-
-        self.inputs.push(input);
-
-        let mut outputs: Vec<i64> = Vec::new();
-
-        loop {
-            if outputs.len() == 2 {
-                return Ok(Some((outputs[0], outputs[1])));
-            }
-
-            let output = self.step()?;
-
-            if self.done {
-                break;
-            }
-
-            if let Some(o) = output {
-                outputs.push(o);
-            }
-        }
-
-        Ok(None)
-    }
-
-    pub fn run_complete_program(&mut self) -> Result<i64, String> {
+    pub fn run(&mut self) -> Result<i64, String> {
         if self.done {
             return Err("can only be run once".to_owned());
         }
@@ -206,7 +169,7 @@ impl IntCode {
         self.outputs
             .last()
             .ok_or_else(|| "no last output".to_owned())
-            .map(|o| *o)
+            .copied()
     }
 
     fn adjust_relative_base(&self, mode: Mode, offset: usize) -> Result<isize, String> {
@@ -335,14 +298,8 @@ impl IntCode {
         Ok(self.ip + 3)
     }
 
-    fn use_input_for_next_state(&mut self, mode: Mode, offset: usize) -> Result<Vec<i64>, String> {
-        let input = if let Some(single_input_value) = self.single_input_value {
-            single_input_value
-        } else {
-            self.inputs.pop().ok_or_else(|| "no input value available".to_owned())?
-        };
-
-        let next_state = self.set_value_for_mode(mode, offset, input)?;
+    fn use_input_for_next_state(&self, mode: Mode, offset: usize) -> Result<Vec<i64>, String> {
+        let next_state = self.set_value_for_mode(mode, offset, self.input_value)?;
 
         Ok(next_state)
     }
@@ -439,8 +396,96 @@ impl IntCode {
     pub fn get_steps(&self) -> usize {
         self.steps
     }
+}
+
+pub static INPUT_PATH: &str = "input/input.txt";
 
-    pub fn is_done(&self) -> bool {
-        self.done
+/// BOOST keycode in test mode
+pub fn part1(path: &Path) -> Result<i64, String> {
+    IntCode::load(path, 1)?.run()
+}
+
+/// coordinates of the distress signal in sensor boost mode
+pub fn part2(path: &Path) -> Result<i64, String> {
+    IntCode::load(path, 2)?.run()
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    fn day(&self) -> u8 {
+        9
+    }
+
+    fn part1(&self, input: &Path) -> Result<String, String> {
+        part1(input).map(|a| a.to_string())
+    }
+
+    fn part2(&self, input: &Path) -> Result<String, String> {
+        part2(input).map(|a| a.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn get_op_code_test_add_correct_output() {
+        let t: &[i64] = &[1, 0, 0, 0, 99];
+        assert_eq!(
+            OpCode::from_i64(t[0]),
+            OpCode::Add {
+                p1_mode: Mode::Position,
+                p2_mode: Mode::Position,
+                p3_mode: Mode::Position
+            }
+        );
+
+        let t2: &[i64] = &[101, 0, 0, 0, 99];
+        assert_eq!(
+            OpCode::from_i64(t2[0]),
+            OpCode::Add {
+                p1_mode: Mode::Immediate,
+                p2_mode: Mode::Position,
+                p3_mode: Mode::Position
+            }
+        );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_program_quine() {
+        let state: Vec<i64> = vec![
+            109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+        ];
+
+        let mut int_code = IntCode::new(
+            state.clone(),
+            0,
+        );
+
+        int_code.set_ignore_outputs(true);
+
+        let output = int_code.run();
+        assert!(output.is_ok());
+
+        let all_outputs = int_code.get_all_outputs();
+        assert_eq!(*all_outputs, state);
+    }
+
+    #[test]
+    fn test_program_middle() {
+        let mut int_code = IntCode::new(vec![104, 1_125_899_906_842_624, 99], 0);
+        let output = int_code.run();
+        assert!(output.is_ok());
+        assert_eq!(output.unwrap(), 1_125_899_906_842_624);
+    }
+
+    #[test]
+    fn test_program_16_digit() {
+        let mut int_code = IntCode::new(vec![1102, 34_915_192, 34_915_192, 7, 4, 7, 99, 0], 0);
+        let output = int_code.run();
+        assert!(output.is_ok());
+        assert_eq!(output.unwrap().to_string().len(), 16);
+    }
+}
@@ -0,0 +1,157 @@
+use std::path::Path;
+
+use std::collections::HashMap;
+
+use aoc_common::{input, Point, Solution};
+
+fn load_asteroids(path: &Path) -> Result<Vec<(usize, usize)>, String> {
+    let input = input::read_to_string(path)?;
+
+    let raw: Vec<Vec<char>> = input.split('\n').map(|l| l.chars().collect()).collect();
+
+    let mut asteroids: Vec<(usize, usize)> = Vec::new();
+
+    raw.iter().enumerate().for_each(|(y, l)| {
+        l.iter().enumerate().for_each(|(x, c)| {
+            if *c == '#' {
+                asteroids.push((x, y));
+            }
+        })
+    });
+
+    Ok(asteroids)
+}
+
+fn find_most_visible(asteroids: &[(usize, usize)]) -> ((usize, usize), usize) {
+    let mut best: Option<(usize, usize)> = None;
+    let mut max_visible = 0;
+
+    asteroids.iter().for_each(|(p_x, p_y)| {
+        let mut angles: Vec<f64> = Vec::new();
+        asteroids.iter().for_each(|(a_x, a_y)| {
+            let dx = *p_x as f64 - *a_x as f64;
+            let dy = *p_y as f64 - *a_y as f64;
+
+            let a = dx.atan2(dy);
+
+            if angles.contains(&a) {
+                return;
+            }
+
+            angles.push(a);
+        });
+
+        if angles.len() > max_visible {
+            best = Some((*p_x, *p_y));
+            max_visible = angles.len();
+        }
+    });
+
+    (best.expect("no best found"), max_visible)
+}
+
+type Angle = isize;
+type Location = (usize, usize);
+type Distance = i64;
+
+fn to_point(l: &Location) -> Point {
+    Point::new(l.0 as i64, l.1 as i64)
+}
+
+fn create_sorted_station_map(
+    asteroids: &[Location],
+    station: &Location,
+) -> HashMap<Angle, Vec<(Location, Distance)>> {
+    let mut map: HashMap<Angle, Vec<(Location, Distance)>> = HashMap::new();
+
+    asteroids
+        .iter()
+        .filter(|l| !(l.0 == station.0 && l.1 == station.1))
+        .for_each(|l| {
+            let dx = station.0 as f64 - l.0 as f64;
+            let dy = station.1 as f64 - l.1 as f64;
+
+            // negative angel so we are iterating clockwise
+            // cast to isize so it can be used as hashmap key
+            // multiplying with 10000 should be enough floating decimals
+            let angle: Angle = -(dx.atan2(dy).to_degrees() * 10000.0) as isize;
+            let distance = to_point(station).manhattan_distance(to_point(l));
+
+            map.entry(angle).or_default().push((*l, distance));
+        });
+
+    // sort map vector entries by distance desc so vector.pop returns closest entry
+    map.iter_mut()
+        .for_each(|(_a, v)| v.sort_by_key(|(_l, d)| -d));
+
+    map
+}
+
+pub static INPUT_PATH: &str = "input/input.txt";
+
+/// `n`th (0 based) asteroid vaporized by the laser rotating clockwise
+/// starting upwards
+fn find_vaporized(
+    asteroids: &[Location],
+    station: &Location,
+    n: usize,
+) -> Result<Location, String> {
+    let mut map = create_sorted_station_map(asteroids, station);
+
+    let mut map_keys: Vec<isize> = map.keys().copied().collect();
+    map_keys.sort();
+
+    // cycle iterator which every cycle first skips all angles < 0
+    // so the laser starts looking up (requirement)
+    let it = map_keys
+        .iter()
+        .cycle()
+        .skip_while(|angle| **angle < 0)
+        .filter_map(|angle| map.get_mut(angle).and_then(|v| v.pop()).map(|(l, _)| l));
+
+    for (c, l) in it.enumerate() {
+        if c == n {
+            return Ok(l);
+        }
+
+        if c > asteroids.len() {
+            return Err(format!(
+                "more laser iterations than available asteroids; {} not found",
+                n + 1
+            ));
+        }
+    }
+
+    Err("no asteroids to vaporize".to_owned())
+}
+
+pub fn part1(path: &Path) -> Result<usize, String> {
+    let asteroids = load_asteroids(path)?;
+
+    Ok(find_most_visible(&asteroids).1)
+}
+
+pub fn part2(path: &Path) -> Result<usize, String> {
+    let asteroids = load_asteroids(path)?;
+    let (station, _) = find_most_visible(&asteroids);
+
+    let l = find_vaporized(&asteroids, &station, 199)?;
+
+    Ok(l.0 * 100 + l.1)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    fn day(&self) -> u8 {
+        10
+    }
+
+    fn part1(&self, input: &Path) -> Result<String, String> {
+        part1(input).map(|a| a.to_string())
+    }
+
+    fn part2(&self, input: &Path) -> Result<String, String> {
+        part2(input).map(|a| a.to_string())
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use int_code::IntCode;
+
+use crate::{Color, Instruction, InstructionSet, Palette};
+
+/// decides what a robot paints and how it moves for the color under it
+pub trait RobotBrain {
+    /// color to paint and instruction, `None` once the brain halted, raw
+    /// outputs are decoded with the robot's palette and instruction set
+    fn decide(
+        &mut self,
+        color: Color,
+        palette: &Palette,
+        instructions: &InstructionSet,
+    ) -> Result<Option<(Color, Instruction)>, String>;
+
+    /// hash of everything influencing future decisions, `None` if the
+    /// brain can't tell, which rules out cycle detection
+    fn state_hash(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// the puzzle's brain, color in, color and instruction out
+impl RobotBrain for IntCode {
+    fn decide(
+        &mut self,
+        color: Color,
+        palette: &Palette,
+        instructions: &InstructionSet,
+    ) -> Result<Option<(Color, Instruction)>, String> {
+        match self.run_for_two_outputs(color.as_i64())? {
+            Some((c, i)) => Ok(Some((palette.get_color(c)?, instructions.decode(i)?))),
+            None => Ok(None),
+        }
+    }
+
+    fn state_hash(&self) -> Option<u64> {
+        let mut hasher = DefaultHasher::new();
+
+        self.get_memory().hash(&mut hasher);
+        self.get_ip().hash(&mut hasher);
+        self.get_relative_base().hash(&mut hasher);
+
+        Some(hasher.finish())
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+struct Rule {
+    color: Color,
+    instruction: Instruction,
+    state: usize,
+}
+
+/// two dimensional Turing machine, the color under it and its state decide
+/// which color it paints, how it turns and its next state, never halts
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Turmite {
+    /// rules per state per color
+    rules: Vec<Vec<Rule>>,
+    state: usize,
+}
+
+impl Turmite {
+    fn parse_turn(c: char) -> Result<Instruction, String> {
+        match c {
+            'L' => Ok(Instruction::TurnLeft),
+            'R' => Ok(Instruction::TurnRight),
+            'N' => Ok(Instruction::Straight),
+            'U' => Ok(Instruction::TurnAround),
+            _ => Err(format!("unknown turn '{}'", c)),
+        }
+    }
+
+    /// one state per `|` separated group, each with a `<color><turn><state>`
+    /// triple per color separated by commas, turns are `L`, `R`, `N` (none)
+    /// and `U` (u-turn), e.g. Langton's ant is `1R0,0L0`
+    pub fn parse(rule: &str) -> Result<Self, String> {
+        let rules: Vec<Vec<Rule>> = rule
+            .split('|')
+            .enumerate()
+            .map(|(s, state)| {
+                state
+                    .split(',')
+                    .map(str::trim)
+                    .enumerate()
+                    .map(|(c, entry)| {
+                        Turmite::parse_rule(entry)
+                            .map_err(|e| format!("state {} color {}: {} ('{}')", s, c, e, entry))
+                    })
+                    .collect::<Result<Vec<Rule>, String>>()
+            })
+            .collect::<Result<Vec<Vec<Rule>>, String>>()?;
+
+        let colors = rules[0].len();
+
+        for (s, state) in rules.iter().enumerate() {
+            if state.len() != colors {
+                return Err(format!(
+                    "state {}: expected {} colors, found {}",
+                    s,
+                    colors,
+                    state.len()
+                ));
+            }
+
+            if let Some(r) = state
+                .iter()
+                .find(|r| r.state >= rules.len() || r.color.as_i64() as usize >= colors)
+            {
+                return Err(format!(
+                    "state {}: color {} or state {} out of range",
+                    s,
+                    r.color.as_i64(),
+                    r.state
+                ));
+            }
+        }
+
+        Ok(Turmite { rules, state: 0 })
+    }
+
+    fn parse_rule(entry: &str) -> Result<Rule, String> {
+        let turn_at = entry
+            .find(|c: char| !c.is_ascii_digit())
+            .ok_or_else(|| "missing turn".to_owned())?;
+        let turn = entry[turn_at..].chars().next().unwrap_or_default();
+
+        let color = entry[..turn_at]
+            .parse::<usize>()
+            .map_err(|e| e.to_string())?;
+        let instruction = Turmite::parse_turn(turn)?;
+        let state = entry[turn_at + turn.len_utf8()..]
+            .parse::<usize>()
+            .map_err(|e| e.to_string())?;
+
+        Ok(Rule {
+            color: Color::new(color),
+            instruction,
+            state,
+        })
+    }
+
+    /// single state turmite with one turn per color, painting the next
+    /// color, e.g. `RL` for Langton's ant or `LLRR`
+    pub fn ant(turns: &str) -> Result<Self, String> {
+        let colors = turns.chars().count();
+
+        let rules = turns
+            .chars()
+            .enumerate()
+            .map(|(c, turn)| {
+                Ok(Rule {
+                    color: Color::new((c + 1) % colors),
+                    instruction: Turmite::parse_turn(turn)
+                        .map_err(|e| format!("color {}: {}", c, e))?,
+                    state: 0,
+                })
+            })
+            .collect::<Result<Vec<Rule>, String>>()?;
+
+        if rules.is_empty() {
+            return Err("ant without turns".to_owned());
+        }
+
+        Ok(Turmite {
+            rules: vec![rules],
+            state: 0,
+        })
+    }
+
+    pub fn langtons_ant() -> Self {
+        Turmite::ant("RL").unwrap()
+    }
+
+    /// number of colors the rules read and paint
+    pub fn colors(&self) -> usize {
+        self.rules[0].len()
+    }
+
+    pub fn get_state(&self) -> usize {
+        self.state
+    }
+}
+
+impl RobotBrain for Turmite {
+    fn decide(
+        &mut self,
+        color: Color,
+        _palette: &Palette,
+        _instructions: &InstructionSet,
+    ) -> Result<Option<(Color, Instruction)>, String> {
+        let rule = self.rules[self.state]
+            .get(color.as_i64() as usize)
+            .ok_or_else(|| format!("turmite has no rule for color {}", color.as_i64()))?;
+
+        self.state = rule.state;
+
+        Ok(Some((rule.color, rule.instruction)))
+    }
+
+    fn state_hash(&self) -> Option<u64> {
+        Some(self.state as u64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_langtons_ant_same_as_ant() {
+        assert_eq!(Turmite::parse("1R0,0L0"), Ok(Turmite::langtons_ant()));
+        assert_eq!(Turmite::ant("LLRR").map(|t| t.colors()), Ok(4));
+    }
+
+    #[test]
+    fn parse_invalid_rule_error() {
+        assert_eq!(
+            Turmite::parse("1R0,0X0"),
+            Err("state 0 color 1: unknown turn 'X' ('0X0')".to_owned())
+        );
+        assert_eq!(
+            Turmite::parse("1R1,0L0|1N0"),
+            Err("state 1: expected 2 colors, found 1".to_owned())
+        );
+        assert_eq!(
+            Turmite::parse("1R2,0L0"),
+            Err("state 0: color 1 or state 2 out of range".to_owned())
+        );
+    }
+
+    #[test]
+    fn decide_follows_states() {
+        // fibonacci turmite
+        let mut turmite = Turmite::parse("1L1,1L1|1R1,0N0").unwrap();
+        let palette = Palette::default();
+        let instructions = InstructionSet::default();
+
+        assert_eq!(
+            turmite.decide(Color::BLACK, &palette, &instructions),
+            Ok(Some((Color::WHITE, Instruction::TurnLeft)))
+        );
+        assert_eq!(turmite.get_state(), 1);
+        assert_eq!(
+            turmite.decide(Color::WHITE, &palette, &instructions),
+            Ok(Some((Color::BLACK, Instruction::Straight)))
+        );
+        assert_eq!(turmite.get_state(), 0);
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+use aoc_common::Point;
+
+use crate::{Color, Heading};
+
+/// what the robot sees and is before a step, positions are relative so a
+/// pattern moving across the hull repeats as well
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct Configuration {
+    pub heading: Heading,
+    pub brain_state: u64,
+    /// colors around the robot row by row
+    pub neighborhood: Vec<Color>,
+}
+
+/// steps `start..` repeat every `length` steps
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+    /// panels painted for the first time per cycle
+    pub painted_per_cycle: usize,
+    /// robot movement per cycle
+    pub shift: Point,
+    /// panels painted at least once after `0..start + length` steps
+    painted: Vec<usize>,
+}
+
+impl Cycle {
+    /// panels painted at least once after `steps` steps
+    pub fn painted_after(&self, steps: u64) -> u64 {
+        if steps < self.painted.len() as u64 {
+            return self.painted[steps as usize] as u64;
+        }
+
+        let offset = steps - self.start as u64;
+        let cycles = offset / self.length as u64;
+        let rest = (offset % self.length as u64) as usize;
+
+        self.painted[self.start + rest] as u64 + cycles * self.painted_per_cycle as u64
+    }
+}
+
+/// finds the shortest period of the configurations a robot passes through,
+/// a period counts once it repeated `confirmations` times with the same
+/// number of new panels and the same movement each time
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct CycleDetector {
+    confirmations: usize,
+    configurations: Vec<Configuration>,
+    positions: Vec<Point>,
+    painted: Vec<usize>,
+    last_seen: HashMap<Configuration, usize>,
+}
+
+impl CycleDetector {
+    pub fn new(confirmations: usize) -> Self {
+        CycleDetector {
+            confirmations: confirmations.max(1),
+            configurations: Vec::new(),
+            positions: Vec::new(),
+            painted: Vec::new(),
+            last_seen: HashMap::new(),
+        }
+    }
+
+    /// records the state before the next step, returns the cycle once it is
+    /// confirmed
+    pub fn observe(
+        &mut self,
+        configuration: Configuration,
+        position: Point,
+        painted: usize,
+    ) -> Option<Cycle> {
+        let step = self.configurations.len();
+        let previous = self.last_seen.insert(configuration.clone(), step);
+
+        self.configurations.push(configuration);
+        self.positions.push(position);
+        self.painted.push(painted);
+
+        let length = step - previous?;
+        let start = step.checked_sub(length * self.confirmations)?;
+
+        if !self.is_periodic(start, length) {
+            return None;
+        }
+
+        Some(Cycle {
+            start,
+            length,
+            painted_per_cycle: self.painted[start + length] - self.painted[start],
+            shift: self.positions[start + length] - self.positions[start],
+            painted: self.painted[..start + length].to_vec(),
+        })
+    }
+
+    fn is_periodic(&self, start: usize, length: usize) -> bool {
+        let painted = self.painted[start + length] - self.painted[start];
+        let shift = self.positions[start + length] - self.positions[start];
+
+        (start + length..self.configurations.len()).all(|i| {
+            self.configurations[i] == self.configurations[i - length]
+                && self.painted[i] - self.painted[i - length] == painted
+                && self.positions[i] - self.positions[i - length] == shift
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn configuration(brain_state: u64) -> Configuration {
+        Configuration {
+            heading: Heading::UP,
+            brain_state,
+            neighborhood: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn observe_confirmed_cycle_correct_output() {
+        let mut detector = CycleDetector::new(2);
+        let states = [9, 1, 2, 1, 2, 1, 2];
+
+        let cycles: Vec<Option<Cycle>> = states
+            .iter()
+            .enumerate()
+            .map(|(i, s)| detector.observe(configuration(*s), Point::new(i as i64, 0), i))
+            .collect();
+
+        assert!(cycles[..5].iter().all(Option::is_none));
+
+        let cycle = cycles[5].clone().unwrap();
+        assert_eq!((cycle.start, cycle.length), (1, 2));
+        assert_eq!(cycle.painted_per_cycle, 2);
+        assert_eq!(cycle.shift, Point::new(2, 0));
+        assert_eq!(cycle.painted_after(1_000_000_000), 1_000_000_000);
+    }
+
+    #[test]
+    fn observe_changing_painted_count_no_cycle() {
+        let mut detector = CycleDetector::new(2);
+        let painted = [0, 1, 1, 3, 3, 6, 6];
+
+        assert!(painted.iter().all(|p| detector
+            .observe(configuration(1), Point::ORIGIN, *p)
+            .is_none()));
+    }
+}
@@ -0,0 +1,255 @@
+use std::collections::HashSet;
+
+use crate::{Color, Heading, HullMap, InstructionSet, Palette, RobotBrain};
+use aoc_common::{grid, Grid, Point, SparseGrid};
+
+/// what happens when a robot wants to move onto a panel another robot is on
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Collision {
+    /// the robot turns but stays where it is
+    Block,
+    /// both robots trade places
+    Swap,
+    /// robots share the panel, whoever paints it last wins
+    Overwrite,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct RobotStats {
+    pub steps: usize,
+    /// distinct panels this robot painted
+    pub panels_painted: usize,
+    /// panels painted by this robot whose color another robot set before
+    pub panels_overpainted: usize,
+    pub blocked: usize,
+    pub swapped: usize,
+    pub halted: bool,
+}
+
+struct FleetRobot {
+    brain: Box<dyn RobotBrain>,
+    position: Point,
+    heading: Heading,
+    painted: HashSet<Point>,
+    stats: RobotStats,
+}
+
+/// robots with their own brains painting one hull, taking turns in
+/// the order they were added
+pub struct Fleet {
+    map: HullMap,
+    /// robot that painted a panel last
+    painters: SparseGrid<usize>,
+    robots: Vec<FleetRobot>,
+    collision: Collision,
+    palette: Palette,
+    instructions: InstructionSet,
+}
+
+impl Fleet {
+    pub fn new(collision: Collision) -> Self {
+        Fleet {
+            map: HullMap::new(),
+            painters: SparseGrid::new(),
+            robots: Vec::new(),
+            collision,
+            palette: Palette::default(),
+            instructions: InstructionSet::default(),
+        }
+    }
+
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.palette = palette;
+    }
+
+    pub fn set_instructions(&mut self, instructions: InstructionSet) {
+        self.instructions = instructions;
+    }
+
+    pub fn set_panel(&mut self, p: Point, color: Color) {
+        self.map.set(p, (color, 0));
+    }
+
+    /// index of the new robot, used for its stats
+    pub fn add_robot<B>(&mut self, brain: B, position: Point, heading: Heading) -> usize
+    where
+        B: RobotBrain + 'static,
+    {
+        self.robots.push(FleetRobot {
+            brain: Box::new(brain),
+            position,
+            heading,
+            painted: HashSet::new(),
+            stats: RobotStats::default(),
+        });
+
+        self.robots.len() - 1
+    }
+
+    pub fn get_stats(&self, robot: usize) -> Option<&RobotStats> {
+        self.robots.get(robot).map(|r| &r.stats)
+    }
+
+    pub fn get_position(&self, robot: usize) -> Option<Point> {
+        self.robots.get(robot).map(|r| r.position)
+    }
+
+    /// panels painted by any robot, preset panels don't count
+    pub fn get_painted_once(&self) -> usize {
+        self.map
+            .iter()
+            .filter(|(_, (_, visits))| *visits > 0)
+            .count()
+    }
+
+    fn get_color(&self, p: Point) -> Color {
+        self.map.get(p).map(|(c, _)| *c).unwrap_or_default()
+    }
+
+    /// one turn of every robot still running, `false` once all halted
+    pub fn step(&mut self) -> Result<bool, String> {
+        for i in 0..self.robots.len() {
+            if !self.robots[i].stats.halted {
+                self.step_robot(i)?;
+            }
+        }
+
+        Ok(self.robots.iter().any(|r| !r.stats.halted))
+    }
+
+    fn step_robot(&mut self, i: usize) -> Result<(), String> {
+        let position = self.robots[i].position;
+        let current = self.get_color(position);
+
+        let decision = self.robots[i]
+            .brain
+            .decide(current, &self.palette, &self.instructions)?;
+
+        let (color, instruction) = match decision {
+            Some(decision) => decision,
+            None => {
+                self.robots[i].stats.halted = true;
+                return Ok(());
+            }
+        };
+
+        self.palette.get_color(color.as_i64())?;
+
+        let (panel_color, visits) = self.map.entry(position).or_default();
+        *panel_color = color;
+        *visits += 1;
+
+        let previous_painter = self.painters.set(position, i);
+
+        let robot = &mut self.robots[i];
+        robot.stats.steps += 1;
+        if robot.painted.insert(position) {
+            robot.stats.panels_painted += 1;
+        }
+        if previous_painter.is_some_and(|p| p != i) {
+            robot.stats.panels_overpainted += 1;
+        }
+
+        robot.heading = instruction.apply(robot.heading);
+        let target = robot.position + robot.heading.delta();
+
+        let occupant =
+            (0..self.robots.len()).find(|j| *j != i && self.robots[*j].position == target);
+
+        match (occupant, self.collision) {
+            (None, _) | (Some(_), Collision::Overwrite) => self.robots[i].position = target,
+            (Some(_), Collision::Block) => self.robots[i].stats.blocked += 1,
+            (Some(j), Collision::Swap) => {
+                self.robots[j].position = position;
+                self.robots[i].position = target;
+                self.robots[i].stats.swapped += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// runs until all robots halted, returns the number of painted panels
+    pub fn run(&mut self) -> Result<usize, String> {
+        while self.step()? {}
+
+        Ok(self.get_painted_once())
+    }
+
+    /// painted hull with robots shown by their index, `+` for 10 and more
+    pub fn render(&self) -> Result<String, String> {
+        let painted = self.map.iter().map(|(p, _)| p);
+        let robots = self.robots.iter().map(|r| r.position);
+
+        let (min, max) =
+            grid::bounding_box(painted.chain(robots)).ok_or_else(|| "empty hull".to_owned())?;
+
+        Ok(grid::render(min, max, |p| {
+            match self.robots.iter().position(|r| r.position == p) {
+                Some(i) => std::char::from_digit(i as u32, 10).unwrap_or('+'),
+                None => self.palette.get_pixel(self.get_color(p)),
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::Direction;
+    use int_code::IntCode;
+
+    /// paints white and outputs instruction `1` `n` times, then halts
+    fn painter(n: usize) -> IntCode {
+        let mut program: Vec<i64> = Vec::new();
+        (0..n).for_each(|_| program.extend(&[3, 1000, 104, 1, 104, 1]));
+        program.push(99);
+
+        IntCode::new(program, None)
+    }
+
+    #[test]
+    fn run_without_collisions_correct_stats() {
+        let mut fleet = Fleet::new(Collision::Block);
+        fleet.add_robot(painter(4), Point::ORIGIN, Heading::UP);
+        fleet.add_robot(painter(2), Point::new(5, 0), Heading::UP);
+
+        assert_eq!(fleet.run(), Ok(6));
+        assert_eq!(
+            fleet.get_stats(1),
+            Some(&RobotStats {
+                steps: 2,
+                panels_painted: 2,
+                halted: true,
+                ..RobotStats::default()
+            })
+        );
+        assert_eq!(fleet.render(), Ok("0#   ##\n##    1".to_owned()));
+    }
+
+    #[test]
+    fn run_collision_rules_correct_output() {
+        let run = |collision: Collision| {
+            let mut fleet = Fleet::new(collision);
+            fleet.set_instructions(InstructionSet::new(vec![
+                crate::Instruction::TurnLeft,
+                crate::Instruction::Straight,
+            ]));
+            fleet.add_robot(painter(1), Point::ORIGIN, Direction::Right.into());
+            fleet.add_robot(painter(1), Point::new(1, 0), Direction::Left.into());
+            fleet.step().unwrap();
+
+            (fleet.get_position(0), fleet.get_stats(0).cloned().unwrap())
+        };
+
+        let (position, stats) = run(Collision::Block);
+        assert_eq!((position, stats.blocked), (Some(Point::ORIGIN), 1));
+
+        let (position, stats) = run(Collision::Swap);
+        assert_eq!((position, stats.swapped), (Some(Point::new(1, 0)), 1));
+
+        let (position, stats) = run(Collision::Overwrite);
+        assert_eq!(position, Some(Point::new(1, 0)));
+        assert_eq!(stats.panels_overpainted, 0);
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use aoc_common::Point;
+
+use crate::{Color, Heading};
+
+/// one paint-and-move of the robot, `heading` is the one it faced while
+/// painting
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Event {
+    pub step: usize,
+    pub position: Point,
+    pub heading: Heading,
+    pub color: Color,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct History {
+    events: Vec<Event>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        History::default()
+    }
+
+    pub fn record(&mut self, position: Point, heading: Heading, color: Color) {
+        self.events.push(Event {
+            step: self.events.len(),
+            position,
+            heading,
+            color,
+        });
+    }
+
+    pub fn get_events(&self) -> &[Event] {
+        &self.events
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// number of times each panel was painted
+    pub fn get_visits(&self) -> HashMap<Point, usize> {
+        let mut visits: HashMap<Point, usize> = HashMap::new();
+
+        self.events
+            .iter()
+            .for_each(|e| *visits.entry(e.position).or_insert(0) += 1);
+
+        visits
+    }
+
+    /// panel painted most often with its count, ties go to the smallest
+    /// point
+    pub fn get_most_visited(&self) -> Option<(Point, usize)> {
+        self.get_visits()
+            .into_iter()
+            .max_by(|(p1, v1), (p2, v2)| v1.cmp(v2).then(p2.cmp(p1)))
+    }
+
+    /// sorted panels painted at least twice
+    pub fn get_painted_more_than_once(&self) -> Vec<Point> {
+        let mut panels: Vec<Point> = self
+            .get_visits()
+            .into_iter()
+            .filter(|(_, v)| *v > 1)
+            .map(|(p, _)| p)
+            .collect();
+
+        panels.sort();
+
+        panels
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::Direction;
+
+    fn history() -> History {
+        let mut history = History::new();
+
+        history.record(Point::new(0, 0), Direction::Up.into(), Color::WHITE);
+        history.record(Point::new(-1, 0), Direction::Left.into(), Color::BLACK);
+        history.record(Point::new(0, 0), Direction::Right.into(), Color::BLACK);
+        history.record(Point::new(-1, 0), Direction::Left.into(), Color::WHITE);
+        history.record(Point::new(5, 5), Direction::Down.into(), Color::WHITE);
+
+        history
+    }
+
+    #[test]
+    fn record_numbers_steps() {
+        let history = history();
+
+        assert_eq!(history.len(), 5);
+        assert_eq!(
+            history.get_events()[2],
+            Event {
+                step: 2,
+                position: Point::new(0, 0),
+                heading: Direction::Right.into(),
+                color: Color::BLACK,
+            }
+        );
+    }
+
+    #[test]
+    fn get_most_visited_correct_output() {
+        assert_eq!(history().get_most_visited(), Some((Point::new(-1, 0), 2)));
+        assert_eq!(History::new().get_most_visited(), None);
+    }
+
+    #[test]
+    fn get_painted_more_than_once_correct_output() {
+        assert_eq!(
+            history().get_painted_more_than_once(),
+            vec![Point::new(-1, 0), Point::new(0, 0)]
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use aoc_common::{Direction, Point};
+
+/// one of eight compass headings, in eighths of a full turn clockwise from
+/// `Up`
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct Heading(u8);
+
+impl Heading {
+    pub const UP: Heading = Heading(0);
+
+    /// turns by `eighths`, negative values turn left
+    pub fn turn(self, eighths: i8) -> Self {
+        Heading((self.0 as i8 + eighths).rem_euclid(8) as u8)
+    }
+
+    /// orthogonal direction, `None` while heading diagonally
+    pub fn as_direction(self) -> Option<Direction> {
+        match self.0 {
+            0 => Some(Direction::Up),
+            2 => Some(Direction::Right),
+            4 => Some(Direction::Down),
+            6 => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    pub fn delta(self) -> Point {
+        match self.0 {
+            0 => Point::new(0, -1),
+            1 => Point::new(1, -1),
+            2 => Point::new(1, 0),
+            3 => Point::new(1, 1),
+            4 => Point::new(0, 1),
+            5 => Point::new(-1, 1),
+            6 => Point::new(-1, 0),
+            _ => Point::new(-1, -1),
+        }
+    }
+
+    /// arrow like char to show the robot
+    pub fn as_pixel(self) -> char {
+        match self.0 {
+            0 => '^',
+            1 | 5 => '/',
+            2 => '>',
+            3 | 7 => '\\',
+            4 => 'v',
+            _ => '<',
+        }
+    }
+
+    /// inverse of `as_pixel` for the orthogonal arrows
+    pub fn from_pixel(pixel: char) -> Option<Self> {
+        match pixel {
+            '^' => Some(Heading(0)),
+            '>' => Some(Heading(2)),
+            'v' => Some(Heading(4)),
+            '<' => Some(Heading(6)),
+            _ => None,
+        }
+    }
+}
+
+impl From<Direction> for Heading {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Heading(0),
+            Direction::Right => Heading(2),
+            Direction::Down => Heading(4),
+            Direction::Left => Heading(6),
+        }
+    }
+}
+
+/// what the robot does after painting, it always moves one panel forward
+/// afterwards
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Instruction {
+    TurnLeft,
+    TurnRight,
+    TurnAround,
+    Straight,
+    /// turns by 45 degrees, the robot moves diagonally until turned again
+    TurnHalfLeft,
+    TurnHalfRight,
+}
+
+impl Instruction {
+    pub fn apply(self, heading: Heading) -> Heading {
+        match self {
+            Instruction::TurnLeft => heading.turn(-2),
+            Instruction::TurnRight => heading.turn(2),
+            Instruction::TurnAround => heading.turn(4),
+            Instruction::Straight => heading,
+            Instruction::TurnHalfLeft => heading.turn(-1),
+            Instruction::TurnHalfRight => heading.turn(1),
+        }
+    }
+}
+
+/// maps the second output of a brain to an instruction by index
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct InstructionSet {
+    instructions: Vec<Instruction>,
+}
+
+impl Default for InstructionSet {
+    /// the puzzle's `0` turns left, `1` turns right
+    fn default() -> Self {
+        InstructionSet {
+            instructions: vec![Instruction::TurnLeft, Instruction::TurnRight],
+        }
+    }
+}
+
+impl InstructionSet {
+    pub fn new(instructions: Vec<Instruction>) -> Self {
+        InstructionSet { instructions }
+    }
+
+    pub fn decode(&self, i: i64) -> Result<Instruction, String> {
+        if i < 0 {
+            return Err(format!("Unknown instruction: {}", i));
+        }
+
+        self.instructions
+            .get(i as usize)
+            .copied()
+            .ok_or_else(|| format!("Unknown instruction: {}", i))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn apply_correct_output() {
+        let heading = Heading::from(Direction::Left);
+
+        assert_eq!(
+            Instruction::TurnRight.apply(heading).as_direction(),
+            Some(Direction::Up)
+        );
+        assert_eq!(
+            Instruction::TurnAround.apply(heading).as_direction(),
+            Some(Direction::Right)
+        );
+        assert_eq!(
+            Instruction::TurnHalfRight.apply(heading).delta(),
+            Point::new(-1, -1)
+        );
+        assert_eq!(
+            Instruction::TurnHalfLeft.apply(Heading::UP).as_direction(),
+            None
+        );
+        assert_eq!(Heading::from_pixel(heading.as_pixel()), Some(heading));
+    }
+
+    #[test]
+    fn decode_unknown_instruction_error() {
+        let instructions = InstructionSet::default();
+
+        assert_eq!(instructions.decode(0), Ok(Instruction::TurnLeft));
+        assert_eq!(
+            instructions.decode(2),
+            Err("Unknown instruction: 2".to_owned())
+        );
+        assert_eq!(
+            instructions.decode(-1),
+            Err("Unknown instruction: -1".to_owned())
+        );
+    }
+}
@@ -0,0 +1,677 @@
+use std::path::Path;
+
+use aoc_common::image::{self, Rgb, BLACK};
+use aoc_common::{grid, input, ocr, Grid, Image, Point, Solution, SparseGrid};
+use int_code::IntCode;
+
+mod brain;
+mod cycle;
+mod fleet;
+mod history;
+mod instruction;
+mod palette;
+
+pub use brain::{RobotBrain, Turmite};
+pub use cycle::{Configuration, Cycle, CycleDetector};
+pub use fleet::{Collision, Fleet, RobotStats};
+pub use history::{Event, History};
+pub use instruction::{Heading, Instruction, InstructionSet};
+pub use palette::{Color, Palette};
+
+pub(crate) type HullMap = SparseGrid<(Color, usize)>;
+
+const ROBOT_RGB: Rgb = [255, 0, 0];
+
+/// periods a configuration sequence has to repeat before it counts as cycle
+const CYCLE_CONFIRMATIONS: usize = 3;
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct HullRobot {
+    map: HullMap,
+    /// colors before the first step, to replay the history
+    start: SparseGrid<Color>,
+    position: Point,
+    heading: Heading,
+    history: History,
+    /// panels painted at least once by the robot
+    painted: usize,
+    palette: Palette,
+    instructions: InstructionSet,
+}
+
+impl Default for HullRobot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HullRobot {
+    pub fn new() -> Self {
+        HullRobot {
+            map: HullMap::new(),
+            start: SparseGrid::new(),
+            position: Point::ORIGIN,
+            heading: Heading::UP,
+            history: History::new(),
+            painted: 0,
+            palette: Palette::default(),
+            instructions: InstructionSet::default(),
+        }
+    }
+
+    pub fn new_with_data(data: &[(Point, Color)]) -> Self {
+        let mut robot = HullRobot::new();
+        data.iter().for_each(|(p, c)| {
+            robot.map.set(*p, (*c, 0));
+            robot.start.set(*p, *c);
+        });
+
+        robot
+    }
+
+    /// hull drawn with the palette's chars, panels numbered from the top
+    /// left, an arrow (`^`, `>`, `v` or `<`) marks where the robot starts
+    /// and its heading, otherwise it starts top left facing up
+    pub fn from_ascii(text: &str, palette: Palette) -> Result<Self, String> {
+        let mut robot = HullRobot::new();
+        let mut robots = 0;
+
+        for (y, line) in text.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let p = Point::new(x as i64, y as i64);
+
+                if let Some(heading) = Heading::from_pixel(c) {
+                    robot.position = p;
+                    robot.heading = heading;
+                    robots += 1;
+                    continue;
+                }
+
+                let color = palette
+                    .find_pixel(c)
+                    .ok_or_else(|| format!("line {}: unknown panel '{}'", y + 1, c))?;
+                robot.set_start_panel(p, color);
+            }
+        }
+
+        if robots > 1 {
+            return Err(format!("{} robots on the hull", robots));
+        }
+
+        robot.set_palette(palette);
+
+        Ok(robot)
+    }
+
+    /// one panel per pixel with the closest palette color, a red pixel as
+    /// in the animation marks where the robot starts facing up, otherwise it
+    /// starts top left
+    pub fn from_image(image: &Image, palette: Palette) -> Result<Self, String> {
+        let mut robot = HullRobot::new();
+        let mut robots = 0;
+
+        for y in 0..image.get_height() {
+            for x in 0..image.get_width() {
+                let p = Point::new(x as i64, y as i64);
+
+                match image.get_pixel(x, y) {
+                    Some(ROBOT_RGB) => {
+                        robot.position = p;
+                        robots += 1;
+                    }
+                    Some(rgb) => robot.set_start_panel(p, palette.find_rgb(rgb)),
+                    None => {}
+                }
+            }
+        }
+
+        if robots > 1 {
+            return Err(format!("{} robots on the hull", robots));
+        }
+
+        robot.set_palette(palette);
+
+        Ok(robot)
+    }
+
+    /// starting hull from a PNG, PBM or otherwise ASCII file
+    pub fn load(path: &Path, palette: Palette) -> Result<Self, String> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("png") => HullRobot::from_image(&Image::read_png(path)?, palette),
+            Some("pbm") => HullRobot::from_image(&Image::read_pbm(path)?, palette),
+            _ => HullRobot::from_ascii(&input::read_to_string(path)?, palette),
+        }
+    }
+
+    /// panels of the first color are left out as they look unpainted anyway
+    fn set_start_panel(&mut self, p: Point, color: Color) {
+        if color != Color::BLACK {
+            self.map.set(p, (color, 0));
+            self.start.set(p, color);
+        }
+    }
+
+    fn get_current_color(&mut self) -> Color {
+        self.map.entry(self.position).or_default().0
+    }
+
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.palette = palette;
+    }
+
+    pub fn get_palette(&self) -> &Palette {
+        &self.palette
+    }
+
+    pub fn set_instructions(&mut self, instructions: InstructionSet) {
+        self.instructions = instructions;
+    }
+
+    fn paint_and_move(&mut self, new_color: Color, instruction: Instruction) {
+        let (color, visits) = self.map.entry(self.position).or_default();
+
+        *color = new_color;
+        *visits += 1;
+
+        if *visits == 1 {
+            self.painted += 1;
+        }
+
+        self.history.record(self.position, self.heading, new_color);
+
+        self.heading = instruction.apply(self.heading);
+        self.position = self.position + self.heading.delta();
+    }
+
+    /// panels the robot painted, colors of the starting hull don't count
+    pub fn get_painted_once(&self) -> usize {
+        self.painted
+    }
+
+    pub fn get_history(&self) -> &History {
+        &self.history
+    }
+
+    pub fn paint_ship(&mut self, int_code: &mut IntCode) -> Result<usize, String> {
+        self.paint(int_code, None)
+    }
+
+    /// lets `brain` paint until it halts or `max_steps` paint-and-moves are
+    /// done, returns the number of panels painted at least once
+    pub fn paint<B>(&mut self, brain: &mut B, max_steps: Option<usize>) -> Result<usize, String>
+    where
+        B: RobotBrain + ?Sized,
+    {
+        let mut steps = 0;
+
+        while max_steps.is_none_or(|m| steps < m) {
+            let color = self.get_current_color();
+
+            let (new_color, instruction) =
+                match brain.decide(color, &self.palette, &self.instructions)? {
+                    Some(decision) => decision,
+                    None => break,
+                };
+
+            // native brains are not decoded by the palette
+            self.palette.get_color(new_color.as_i64())?;
+
+            self.paint_and_move(new_color, instruction);
+            steps += 1;
+        }
+
+        Ok(self.get_painted_once())
+    }
+
+    /// colors of the square with `radius` around the robot, row by row
+    fn get_neighborhood(&self, radius: i64) -> Vec<Color> {
+        (-radius..=radius)
+            .flat_map(|y| (-radius..=radius).map(move |x| Point::new(x, y)))
+            .map(|d| HullRobot::get_color(self.map.get(self.position + d)))
+            .collect()
+    }
+
+    /// paints like `paint` until the configuration of heading, brain state
+    /// and the colors within `radius` starts repeating, `None` if no cycle
+    /// is confirmed within `max_steps` or the brain halts
+    pub fn find_cycle<B>(
+        &mut self,
+        brain: &mut B,
+        radius: i64,
+        max_steps: usize,
+    ) -> Result<Option<Cycle>, String>
+    where
+        B: RobotBrain + ?Sized,
+    {
+        let mut detector = CycleDetector::new(CYCLE_CONFIRMATIONS);
+
+        for _ in 0..=max_steps {
+            let configuration = Configuration {
+                heading: self.heading,
+                brain_state: brain
+                    .state_hash()
+                    .ok_or_else(|| "brain state unknown, can't detect cycles".to_owned())?,
+                neighborhood: self.get_neighborhood(radius),
+            };
+
+            let painted = self.get_painted_once();
+
+            if let Some(cycle) = detector.observe(configuration, self.position, painted) {
+                return Ok(Some(cycle));
+            }
+
+            let before = self.history.len();
+            self.paint(brain, Some(1))?;
+
+            if self.history.len() == before {
+                return Ok(None);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// bounding box (min, max) of all visited panels
+    pub fn get_painted_coords_system(&self) -> Result<(Point, Point), String> {
+        self.map
+            .bounds()
+            .ok_or_else(|| "nothing painted".to_owned())
+    }
+
+    fn get_color(panel: Option<&(Color, usize)>) -> Color {
+        panel.map(|(c, _)| *c).unwrap_or_default()
+    }
+
+    pub fn render_painted_hull(&self) -> Result<String, String> {
+        self.get_painted_coords_system()?;
+
+        Ok(self
+            .map
+            .render(|panel| self.palette.get_pixel(HullRobot::get_color(panel))))
+    }
+
+    /// like `render_painted_hull` with two rows of panels per line
+    pub fn render_painted_hull_compact(&self) -> Result<String, String> {
+        self.get_painted_coords_system()?;
+
+        Ok(self
+            .map
+            .render_half_blocks(|panel| HullRobot::get_color(panel) != Color::BLACK))
+    }
+
+    /// letters painted in any color but the first
+    pub fn read_painted_hull(&self) -> Result<String, String> {
+        ocr::recognize(&self.map, |panel| {
+            HullRobot::get_color(panel) != Color::BLACK
+        })
+    }
+
+    /// one pixel per panel of the bounding box
+    pub fn painted_hull_image(&self) -> Result<Image, String> {
+        self.get_painted_coords_system()?;
+
+        Ok(self
+            .map
+            .to_image(|panel| self.palette.get_rgb(HullRobot::get_color(panel))))
+    }
+
+    /// number of paints per panel, ` ` for none and `+` for 10 and more
+    pub fn render_visits(&self) -> Result<String, String> {
+        self.get_painted_coords_system()?;
+
+        Ok(self.map.render(|panel| match panel {
+            Some((_, visits)) if *visits > 0 => {
+                std::char::from_digit(*visits as u32, 10).unwrap_or('+')
+            }
+            _ => ' ',
+        }))
+    }
+
+    /// number of paints per panel from black for none to white for the
+    /// most painted panel
+    pub fn visits_heatmap(&self) -> Result<Image, String> {
+        self.get_painted_coords_system()?;
+
+        let max = self.map.iter().map(|(_, (_, v))| *v).max().unwrap_or(0);
+
+        Ok(self.map.to_image(|panel| match panel {
+            Some((_, visits)) if *visits > 0 => image::heat(*visits as f64 / max as f64),
+            _ => BLACK,
+        }))
+    }
+
+    /// bounding box of everything the animation shows, stays the same for
+    /// all frames
+    fn get_animation_bounds(&self) -> Result<(Point, Point), String> {
+        let start = self.start.iter().map(|(p, _)| p);
+        let painted = self.history.get_events().iter().map(|e| e.position);
+
+        grid::bounding_box(start.chain(painted).chain(Some(self.position)))
+            .ok_or_else(|| "nothing to animate".to_owned())
+    }
+
+    /// calls `frame` with colors, robot position and heading before the
+    /// first and after every `every`-th step, the final state is always
+    /// included
+    fn replay<F>(&self, every: usize, mut frame: F)
+    where
+        F: FnMut(&SparseGrid<Color>, Point, Heading),
+    {
+        let mut colors = self.start.clone();
+        let events = self.history.get_events();
+        let every = every.max(1);
+
+        for (i, event) in events.iter().enumerate() {
+            if i % every == 0 {
+                frame(&colors, event.position, event.heading);
+            }
+
+            colors.set(event.position, event.color);
+        }
+
+        frame(&colors, self.position, self.heading);
+    }
+
+    /// hull with the robot as arrow, one string per frame
+    pub fn render_animation(&self, every: usize) -> Result<Vec<String>, String> {
+        let (min, max) = self.get_animation_bounds()?;
+        let mut frames: Vec<String> = Vec::new();
+
+        self.replay(every, |colors, position, heading| {
+            frames.push(grid::render(min, max, |p| {
+                if p == position {
+                    heading.as_pixel()
+                } else {
+                    let color = colors.get(p).copied().unwrap_or_default();
+                    self.palette.get_pixel(color)
+                }
+            }))
+        });
+
+        Ok(frames)
+    }
+
+    /// like `render_animation` with one pixel per panel and the robot in red
+    pub fn animation_images(&self, every: usize) -> Result<Vec<Image>, String> {
+        let (min, max) = self.get_animation_bounds()?;
+        let (width, height) = ((max.x - min.x + 1) as usize, (max.y - min.y + 1) as usize);
+        let mut frames: Vec<Image> = Vec::new();
+
+        self.replay(every, |colors, position, _| {
+            let pixels: Vec<Rgb> = (min.y..=max.y)
+                .flat_map(|y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+                .map(|p| match colors.get(p) {
+                    _ if p == position => ROBOT_RGB,
+                    c => self.palette.get_rgb(c.copied().unwrap_or_default()),
+                })
+                .collect();
+
+            frames.push(Image::new(width, height, pixels).unwrap());
+        });
+
+        Ok(frames)
+    }
+}
+
+pub static INPUT_PATH: &str = "input/input.txt";
+
+/// panels painted at least once starting on a black panel
+pub fn part1(path: &Path) -> Result<usize, String> {
+    let mut int_code = IntCode::load(path, None)?;
+
+    HullRobot::new().paint_ship(&mut int_code)
+}
+
+/// registration identifier painted starting on a white panel
+pub fn part2(path: &Path) -> Result<String, String> {
+    let mut hull_robot = HullRobot::new_with_data(&[(Point::ORIGIN, Color::WHITE)]);
+    let mut int_code = IntCode::load(path, None)?;
+
+    hull_robot.paint_ship(&mut int_code)?;
+    hull_robot.read_painted_hull()
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    fn day(&self) -> u8 {
+        11
+    }
+
+    fn part1(&self, input: &Path) -> Result<String, String> {
+        part1(input).map(|a| a.to_string())
+    }
+
+    fn part2(&self, input: &Path) -> Result<String, String> {
+        part2(input).map(|a| a.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use int_code::{Mode, OpCode};
+
+    #[test]
+    fn get_op_code_test_add_correct_output() {
+        let t: &[i64] = &[1, 0, 0, 0, 99];
+        assert_eq!(
+            OpCode::from_i64(t[0]),
+            OpCode::Add {
+                p1_mode: Mode::Position,
+                p2_mode: Mode::Position,
+                p3_mode: Mode::Position
+            }
+        );
+
+        let t2: &[i64] = &[101, 0, 0, 0, 99];
+        assert_eq!(
+            OpCode::from_i64(t2[0]),
+            OpCode::Add {
+                p1_mode: Mode::Immediate,
+                p2_mode: Mode::Position,
+                p3_mode: Mode::Position
+            }
+        );
+    }
+
+    #[test]
+    fn test_program_quine() {
+        let state: Vec<i64> = vec![
+            109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+        ];
+
+        let mut int_code = IntCode::new(state.clone(), Some(0));
+
+        int_code.set_ignore_outputs(true);
+
+        let output = int_code.run_complete_program();
+        assert!(output.is_ok());
+
+        let all_outputs = int_code.get_all_outputs();
+        assert_eq!(*all_outputs, state);
+    }
+
+    #[test]
+    fn test_program_middle() {
+        let mut int_code = IntCode::new(vec![104, 1_125_899_906_842_624, 99], Some(0));
+        let output = int_code.run_complete_program();
+        assert!(output.is_ok());
+        assert_eq!(output.unwrap(), 1_125_899_906_842_624);
+    }
+
+    #[test]
+    fn test_program_16_digit() {
+        let mut int_code =
+            IntCode::new(vec![1102, 34_915_192, 34_915_192, 7, 4, 7, 99, 0], Some(0));
+        let output = int_code.run_complete_program();
+        assert!(output.is_ok());
+        assert_eq!(output.unwrap().to_string().len(), 16);
+    }
+
+    #[test]
+    fn render_painted_hull_correct_output() {
+        let robot = HullRobot::new_with_data(&[
+            (Point::new(-1, 0), Color::WHITE),
+            (Point::new(1, 1), Color::WHITE),
+            (Point::new(0, 1), Color::BLACK),
+        ]);
+
+        assert_eq!(robot.render_painted_hull(), Ok("#  \n  #".to_owned()));
+        assert_eq!(
+            robot.render_painted_hull_compact(),
+            Ok("\u{2580} \u{2584}".to_owned())
+        );
+        assert!(HullRobot::new().render_painted_hull().is_err());
+    }
+
+    #[test]
+    fn part2_correct_output() {
+        assert_eq!(part2(Path::new(INPUT_PATH)), Ok("HGEHJHUZ".to_owned()));
+    }
+
+    #[test]
+    fn render_animation_correct_output() {
+        let mut robot = HullRobot::new();
+        robot.paint_and_move(Color::WHITE, Instruction::TurnLeft);
+        robot.paint_and_move(Color::WHITE, Instruction::TurnLeft);
+
+        assert_eq!(
+            robot.render_animation(1),
+            Ok(vec![
+                " ^\n  ".to_owned(),
+                "<#\n  ".to_owned(),
+                "##\nv ".to_owned()
+            ])
+        );
+        assert_eq!(robot.render_animation(5).map(|f| f.len()), Ok(2));
+        assert_eq!(robot.animation_images(1).map(|f| f.len()), Ok(3));
+        assert_eq!(
+            robot.get_history().get_most_visited().map(|(_, v)| v),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn paint_with_palette_and_diagonal_moves_correct_output() {
+        let mut robot = HullRobot::new();
+        robot.set_palette(Palette::from_chars(".+*").unwrap());
+        robot.set_instructions(InstructionSet::new(vec![
+            Instruction::Straight,
+            Instruction::TurnHalfRight,
+            Instruction::TurnAround,
+        ]));
+
+        let palette = robot.get_palette().clone();
+        let instructions = robot.instructions.clone();
+        for (color, instruction) in &[(1, 1), (2, 0), (1, 2), (0, 2)] {
+            robot.paint_and_move(
+                palette.get_color(*color).unwrap(),
+                instructions.decode(*instruction).unwrap(),
+            );
+        }
+
+        assert_eq!(robot.render_painted_hull(), Ok("..+\n...\n+..".to_owned()));
+        assert_eq!(robot.position, Point::new(2, -2));
+        assert!(palette.get_color(3).is_err());
+    }
+
+    #[test]
+    fn paint_langtons_ant_correct_output() {
+        let mut robot = HullRobot::new();
+
+        assert_eq!(robot.paint(&mut Turmite::langtons_ant(), Some(5)), Ok(4));
+        assert_eq!(robot.render_painted_hull(), Ok(" #\n##".to_owned()));
+        assert_eq!(robot.position, Point::new(-1, 0));
+
+        let mut robot = HullRobot::new();
+        assert_eq!(
+            robot.paint(&mut Turmite::ant("RLR").unwrap(), Some(5)),
+            Err("Unknown color: 2".to_owned())
+        );
+    }
+
+    #[test]
+    fn find_cycle_langtons_ant_highway() {
+        let mut robot = HullRobot::new();
+        let cycle = robot
+            .find_cycle(&mut Turmite::langtons_ant(), 2, 20_000)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(cycle.length, 104);
+        assert_eq!(cycle.shift.manhattan_distance(Point::ORIGIN), 4);
+
+        let mut simulated = HullRobot::new();
+        simulated
+            .paint(&mut Turmite::langtons_ant(), Some(15_000))
+            .unwrap();
+
+        assert_eq!(
+            cycle.painted_after(15_000),
+            simulated.get_painted_once() as u64
+        );
+    }
+
+    #[test]
+    fn find_cycle_halting_brain_none() {
+        let mut int_code = IntCode::new(vec![3, 100, 104, 1, 104, 0, 99], None);
+
+        assert_eq!(HullRobot::new().find_cycle(&mut int_code, 1, 100), Ok(None));
+    }
+
+    #[test]
+    fn from_ascii_correct_output() {
+        let palette = Palette::from_chars(".#").unwrap();
+        let mut robot = HullRobot::from_ascii("..#\n.<\n#", palette.clone()).unwrap();
+
+        assert_eq!(robot.position, Point::new(1, 1));
+        assert_eq!(robot.render_painted_hull(), Ok("..#\n...\n#..".to_owned()));
+        assert_eq!(robot.get_painted_once(), 0);
+
+        robot.paint_and_move(Color::WHITE, Instruction::TurnLeft);
+        robot.paint_and_move(Color::WHITE, Instruction::Straight);
+
+        assert_eq!(robot.position, Point::new(1, 3));
+        assert_eq!(robot.get_painted_once(), 2);
+        assert_eq!(robot.render_visits(), Ok("   \n 1 \n 1 ".to_owned()));
+
+        assert_eq!(
+            HullRobot::from_ascii(".x", palette.clone()),
+            Err("line 1: unknown panel 'x'".to_owned())
+        );
+        assert_eq!(
+            HullRobot::from_ascii(">\n<", palette),
+            Err("2 robots on the hull".to_owned())
+        );
+    }
+
+    #[test]
+    fn from_image_same_as_painted_hull_image() {
+        let palette = Palette::from_chars(" .#").unwrap();
+        let mut robot = HullRobot::new();
+        robot.set_palette(palette.clone());
+        robot
+            .paint(&mut Turmite::ant("RLL").unwrap(), Some(50))
+            .unwrap();
+
+        let image = robot.painted_hull_image().unwrap();
+        let imported = HullRobot::from_image(&image, palette).unwrap();
+
+        assert_eq!(imported.render_painted_hull(), robot.render_painted_hull());
+        assert_eq!(imported.position, Point::ORIGIN);
+    }
+
+    #[test]
+    fn visits_heatmap_correct_output() {
+        let mut robot = HullRobot::new();
+        robot.paint_and_move(Color::WHITE, Instruction::TurnAround);
+        robot.paint_and_move(Color::WHITE, Instruction::TurnAround);
+        robot.paint_and_move(Color::WHITE, Instruction::TurnRight);
+
+        let heatmap = robot.visits_heatmap().unwrap();
+
+        assert_eq!(heatmap.get_pixel(0, 0), Some(image::WHITE));
+        assert_eq!(heatmap.get_pixel(0, 1), Some(image::heat(0.5)));
+    }
+}
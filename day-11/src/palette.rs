@@ -0,0 +1,147 @@
+use aoc_common::image::{Rgb, BLACK, WHITE};
+
+/// index into a `Palette`, unpainted panels have the first color
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Clone, Copy, Default)]
+pub struct Color(usize);
+
+impl Color {
+    pub const BLACK: Color = Color(0);
+    pub const WHITE: Color = Color(1);
+
+    pub fn new(index: usize) -> Self {
+        Color(index)
+    }
+
+    pub fn as_i64(self) -> i64 {
+        self.0 as i64
+    }
+}
+
+/// colors a robot may paint with their terminal and image representation
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Palette {
+    colors: Vec<(char, Rgb)>,
+}
+
+impl Default for Palette {
+    /// the puzzle's black and white
+    fn default() -> Self {
+        Palette {
+            colors: vec![(' ', BLACK), ('#', WHITE)],
+        }
+    }
+}
+
+impl Palette {
+    pub fn new(colors: Vec<(char, Rgb)>) -> Result<Self, String> {
+        if colors.is_empty() {
+            return Err("palette without colors".to_owned());
+        }
+
+        Ok(Palette { colors })
+    }
+
+    /// one color per char, shades of gray from black to white
+    pub fn from_chars(pixels: &str) -> Result<Self, String> {
+        let n = pixels.chars().count();
+        let shade = |i: usize| (i * 255 / (n.max(2) - 1)) as u8;
+
+        Palette::new(
+            pixels
+                .chars()
+                .enumerate()
+                .map(|(i, c)| (c, [shade(i); 3]))
+                .collect(),
+        )
+    }
+
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    /// replaces the render chars of the first `pixels.len()` colors
+    pub fn set_pixels(&mut self, pixels: &str) {
+        self.colors
+            .iter_mut()
+            .zip(pixels.chars())
+            .for_each(|((pixel, _), c)| *pixel = c);
+    }
+
+    /// color for the value a brain outputs
+    pub fn get_color(&self, i: i64) -> Result<Color, String> {
+        if i < 0 || i as usize >= self.colors.len() {
+            return Err(format!("Unknown color: {}", i));
+        }
+
+        Ok(Color(i as usize))
+    }
+
+    pub fn get_pixel(&self, color: Color) -> char {
+        self.colors.get(color.0).map_or('?', |(pixel, _)| *pixel)
+    }
+
+    pub fn get_rgb(&self, color: Color) -> Rgb {
+        self.colors.get(color.0).map_or(BLACK, |(_, rgb)| *rgb)
+    }
+
+    /// color rendered as `pixel`
+    pub fn find_pixel(&self, pixel: char) -> Option<Color> {
+        self.colors.iter().position(|(c, _)| *c == pixel).map(Color)
+    }
+
+    /// color closest to `rgb`, ties go to the first
+    pub fn find_rgb(&self, rgb: Rgb) -> Color {
+        let distance = |other: &Rgb| -> i32 {
+            rgb.iter()
+                .zip(other)
+                .map(|(a, b)| (*a as i32 - *b as i32).pow(2))
+                .sum()
+        };
+
+        (0..self.colors.len())
+            .min_by_key(|i| distance(&self.colors[*i].1))
+            .map_or(Color::BLACK, Color)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn get_color_unknown_error() {
+        let palette = Palette::default();
+
+        assert_eq!(palette.get_color(1), Ok(Color::WHITE));
+        assert_eq!(palette.get_color(2), Err("Unknown color: 2".to_owned()));
+        assert_eq!(palette.get_color(-1), Err("Unknown color: -1".to_owned()));
+    }
+
+    #[test]
+    fn from_chars_correct_output() {
+        let mut palette = Palette::from_chars(" .:#").unwrap();
+
+        assert_eq!(palette.len(), 4);
+        assert_eq!(palette.get_pixel(Color::new(2)), ':');
+        assert_eq!(palette.get_rgb(Color::new(1)), [85, 85, 85]);
+        assert_eq!(palette.get_rgb(Color::new(3)), WHITE);
+
+        palette.set_pixels("_");
+        assert_eq!(palette.get_pixel(Color::BLACK), '_');
+        assert!(Palette::from_chars("").is_err());
+    }
+
+    #[test]
+    fn find_pixel_and_rgb_correct_output() {
+        let palette = Palette::from_chars(" .:#").unwrap();
+
+        assert_eq!(palette.find_pixel(':'), Some(Color::new(2)));
+        assert_eq!(palette.find_pixel('x'), None);
+        assert_eq!(palette.find_rgb([80, 90, 100]), Color::new(1));
+        assert_eq!(palette.find_rgb([255, 0, 0]), Color::new(1));
+    }
+}
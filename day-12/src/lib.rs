@@ -0,0 +1,144 @@
+use std::path::Path;
+
+use aoc_common::{input, Point3, Solution};
+
+fn load_input(path: &Path) -> Result<Vec<Point3>, String> {
+    let input_raw = input::read_to_string(path)?;
+
+    input_raw
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .enumerate()
+        .map(|(i, l)| match input::integers(l).as_slice() {
+            [x, y, z] => Ok(Point3::new(*x, *y, *z)),
+            _ => Err(format!("moon {}: expected <x=.., y=.., z=..>: {}", i, l)),
+        })
+        .collect()
+}
+
+pub static INPUT_PATH: &str = "input/input.txt";
+
+fn lcm(a: u64, b: u64) -> u64 {
+    a * b / gcd(a, b)
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    let mut remainder;
+
+    loop {
+        remainder = a % b;
+        a = b;
+        b = remainder;
+
+        if b == 0 {
+            break a;
+        }
+    }
+}
+
+/// moon positions per dimension, `pos[dim][moon]`
+fn into_dimensions(moons: &[Point3]) -> Result<[[i64; 4]; 3], String> {
+    if moons.len() != 4 {
+        return Err(format!("expected 4 moons, found {}", moons.len()));
+    }
+
+    let mut pos = [[0; 4]; 3];
+
+    for (moon, p) in moons.iter().enumerate() {
+        for (dim, v) in p.to_array().iter().enumerate() {
+            pos[dim][moon] = *v;
+        }
+    }
+
+    Ok(pos)
+}
+
+/// dimensions are independent, so a time step can be applied to one at a time
+fn step_dimension(pos: &mut [i64; 4], vel: &mut [i64; 4]) {
+    // apply gravity to each moon
+    for moon1 in 0..4 {
+        for moon2 in moon1 + 1..4 {
+            if pos[moon1] < pos[moon2] {
+                vel[moon1] += 1;
+                vel[moon2] -= 1;
+            } else if pos[moon1] > pos[moon2] {
+                vel[moon1] -= 1;
+                vel[moon2] += 1;
+            }
+        }
+    }
+
+    // adjust velocity
+    for moon in 0..4 {
+        pos[moon] += vel[moon];
+    }
+}
+
+/// total energy after 1000 steps
+pub fn part1(path: &Path) -> Result<i64, String> {
+    let mut pos = into_dimensions(&load_input(path)?)?;
+    let mut vel: [[i64; 4]; 3] = [[0; 4]; 3];
+
+    for _ in 0..1000 {
+        for dim in 0..3 {
+            step_dimension(&mut pos[dim], &mut vel[dim]);
+        }
+    }
+
+    let energy = (0..4)
+        .map(|moon| {
+            let potential: i64 = (0..3).map(|dim| pos[dim][moon].abs()).sum();
+            let kinetic: i64 = (0..3).map(|dim| vel[dim][moon].abs()).sum();
+
+            potential * kinetic
+        })
+        .sum();
+
+    Ok(energy)
+}
+
+/// steps until all moons are back at their initial state
+pub fn part2(path: &Path) -> Result<u64, String> {
+    let mut pos = into_dimensions(&load_input(path)?)?;
+    let mut vel: [[i64; 4]; 3] = [[0; 4]; 3];
+
+    let pos0 = pos;
+    let vel0 = vel;
+
+    let mut periods = [0, 0, 0];
+
+    // calculate periods for each dimension
+    for dim in 0..3 {
+        let mut i: u64 = 0;
+
+        loop {
+            step_dimension(&mut pos[dim], &mut vel[dim]);
+
+            i += 1;
+
+            if pos[dim] == pos0[dim] && vel[dim] == vel0[dim] {
+                break;
+            }
+        }
+
+        periods[dim] = i;
+    }
+
+    Ok(periods.iter().fold(1, |acc, period| lcm(acc, *period)))
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    fn day(&self) -> u8 {
+        12
+    }
+
+    fn part1(&self, input: &Path) -> Result<String, String> {
+        part1(input).map(|a| a.to_string())
+    }
+
+    fn part2(&self, input: &Path) -> Result<String, String> {
+        part2(input).map(|a| a.to_string())
+    }
+}
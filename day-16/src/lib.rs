@@ -0,0 +1,120 @@
+use std::convert::TryInto;
+use std::path::Path;
+
+use aoc_common::{input, Solution};
+
+static BASE_PATTERN: [i8; 4] = [0, 1, 0, -1];
+
+fn load_input(path: &Path) -> Result<Vec<u8>, String> {
+    let raw = input::read_to_string(path)?;
+
+    raw.trim()
+        .chars()
+        .map(|c| {
+            c.to_digit(10)
+                .ok_or_else(|| format!("Could not parse '{}' as digit", c))
+                .map(|u| u as u8)
+        })
+        .collect::<Result<Vec<u8>, String>>()
+}
+
+fn calc_next_phase(input: &[u8]) -> Vec<u8> {
+    let mut output = input.to_owned();
+
+    for (i, o) in output.iter_mut().enumerate().take(input.len()) {
+        let mut val = 0i32;
+        for (mut j, d) in input.iter().skip(i).enumerate() {
+            j += i;
+            let idx = (((j + 1) as i32 / (i + 1) as i32) % 4) as usize;
+            val += *d as i32 * BASE_PATTERN[idx] as i32;
+        }
+        *o = (val.abs() % 10) as u8;
+    }
+
+    output
+}
+
+fn message_start(message: &[u8], n: usize) -> Result<String, String> {
+    message
+        .iter()
+        .take(n)
+        .map(|i| {
+            std::char::from_digit(*i as u32, 10)
+                .ok_or_else(|| format!("Could not parse '{}' as char", i))
+        })
+        .collect::<Result<String, String>>()
+}
+
+fn iterate_phases(i: &mut Vec<u8>, n: usize) {
+    for _ in 0..n {
+        *i = calc_next_phase(i);
+    }
+}
+
+fn digits_to_number(i: &[u8], n: usize) -> u32 {
+    i.iter()
+        .take(n)
+        .rev()
+        .enumerate()
+        .fold(0u32, |acc, (i, x)| acc + (*x as u32) * 10u32.pow(i as u32))
+}
+
+/// phases for the second half of a signal, where every digit is the sum of all
+/// following ones
+fn iterate_phases_tail(input: &[u8], n: usize) -> Vec<u8> {
+    let mut output = input.to_owned();
+
+    for _ in 0..n {
+        for i in (0..input.len() - 1).rev() {
+            output[i] = (output[i] + output[i + 1]) % 10;
+        }
+    }
+
+    output
+}
+
+pub static INPUT_PATH: &str = "input/input.txt";
+
+/// first eight digits after 100 phases
+pub fn part1(path: &Path) -> Result<String, String> {
+    let mut output = load_input(path)?;
+
+    iterate_phases(&mut output, 100);
+
+    message_start(&output, 8)
+}
+
+/// eight digit message at the offset of the 10000 times repeated signal
+pub fn part2(path: &Path) -> Result<String, String> {
+    let input = load_input(path)?;
+
+    let offset = digits_to_number(&input, 7);
+
+    let input2: Vec<u8> = input
+        .iter()
+        .cycle()
+        .take(input.len() * 10000)
+        .skip(offset.try_into().map_err(|_| "invalid offset".to_owned())?)
+        .copied()
+        .collect();
+
+    let output2 = iterate_phases_tail(&input2, 100);
+
+    message_start(&output2, 8)
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    fn day(&self) -> u8 {
+        16
+    }
+
+    fn part1(&self, input: &Path) -> Result<String, String> {
+        part1(input).map(|a| a.to_string())
+    }
+
+    fn part2(&self, input: &Path) -> Result<String, String> {
+        part2(input).map(|a| a.to_string())
+    }
+}
@@ -0,0 +1,177 @@
+use aoc_common::{input, DenseGrid, Grid, Point, Solution};
+use pathfinding::directed::dijkstra::dijkstra;
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::str::FromStr;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Tile {
+    Open,
+    Wall,
+    Entrance,
+    Key(char),
+    /// lowercase name of the key opening it
+    Door(char),
+}
+
+impl Tile {
+    fn from_char(c: char) -> Result<Self, String> {
+        match c {
+            '.' => Ok(Tile::Open),
+            '#' => Ok(Tile::Wall),
+            '@' => Ok(Tile::Entrance),
+            'a'..='z' => Ok(Tile::Key(c)),
+            'A'..='Z' => Ok(Tile::Door(c.to_ascii_lowercase())),
+            _ => Err(format!("unknown char: {}", c)),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Map {
+    tiles: DenseGrid<Tile>,
+    start: Point,
+}
+
+impl Map {
+    pub fn count<F>(&self, f: F) -> usize
+    where
+        F: Fn(&Tile) -> bool,
+    {
+        self.tiles.iter().filter(|(_, t)| f(t)).count()
+    }
+
+    fn get_keys(&self) -> BTreeSet<char> {
+        self.tiles
+            .iter()
+            .filter_map(|(_, t)| match t {
+                Tile::Key(k) => Some(*k),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl FromStr for Map {
+    type Err = String;
+    fn from_str(raw: &str) -> Result<Self, String> {
+        let trimmed = raw.lines().map(str::trim).collect::<Vec<&str>>().join("\n");
+        let tiles = DenseGrid::parse(&trimmed, Tile::from_char)?;
+
+        let start = tiles
+            .iter()
+            .find(|(_, t)| **t == Tile::Entrance)
+            .map(|(p, _)| p)
+            .ok_or_else(|| "no start position found".to_string())?;
+
+        Ok(Map { tiles, start })
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Hash)]
+pub struct MazePos {
+    pub pos: Point,
+    pub required_keys: BTreeSet<char>,
+}
+
+impl MazePos {
+    fn get_neighbor(&self, pos: Point, tile: Tile) -> Option<MazePos> {
+        let mut required_keys = self.required_keys.clone();
+
+        match tile {
+            // walls are never neighbors
+            Tile::Wall => return None,
+            // doors only if we have the key
+            Tile::Door(d) if self.required_keys.contains(&d) => return None,
+            // a key is collected by entering its tile
+            Tile::Key(k) => {
+                required_keys.remove(&k);
+            }
+            _ => {}
+        }
+
+        Some(MazePos { pos, required_keys })
+    }
+
+    pub fn get_neighbors(&self, map: &Map) -> Vec<(MazePos, usize)> {
+        map.tiles
+            .neighbors4(self.pos)
+            .into_iter()
+            .filter_map(|(p, t)| self.get_neighbor(p, *t))
+            .map(|p| (p, 1))
+            .collect::<Vec<(MazePos, usize)>>()
+    }
+}
+
+fn find_shortest_path(map: &Map) -> Option<(std::vec::Vec<MazePos>, usize)> {
+    let required_keys = map.get_keys();
+    let start = MazePos {
+        pos: map.start,
+        required_keys,
+    };
+
+    dijkstra(
+        &start,
+        |p: &MazePos| p.get_neighbors(map),
+        |p: &MazePos| p.required_keys.is_empty(),
+    )
+}
+
+fn load_map(path: &Path) -> Result<Map, String> {
+    let raw = input::read_to_string(path)?;
+    Map::from_str(&raw)
+}
+
+pub const INPUT_PATH: &str = "input/input.txt";
+
+/// fewest steps to collect all keys, part 2 is not solved yet
+pub fn part1(path: &Path) -> Result<usize, String> {
+    let map = load_map(path)?;
+
+    let path = find_shortest_path(&map).ok_or_else(|| "no way found".to_owned())?;
+
+    Ok(path.1)
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    fn day(&self) -> u8 {
+        18
+    }
+
+    fn part1(&self, input: &Path) -> Result<String, String> {
+        part1(input).map(|a| a.to_string())
+    }
+
+    fn part2(&self, _input: &Path) -> Result<String, String> {
+        Err("part 2 is not solved yet".to_owned())
+    }
+
+    fn parts(&self) -> u8 {
+        1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_map_parsing_map1_valid() {
+        let example_map_1 = r"########################
+        #f.D.E.e.C.b.A.@.a.B.c.#
+        ######################.#
+        #d.....................#
+        ########################";
+
+        let map = Map::from_str(example_map_1);
+        assert!(map.is_ok());
+
+        let map = map.unwrap();
+        assert_eq!(map.count(|t| *t == Tile::Wall), 75);
+        assert_eq!(map.get_keys().len(), 6);
+        assert_eq!(map.count(|t| matches!(t, Tile::Door(_))), 5);
+        assert_eq!(map.start, Point::new(15, 1));
+    }
+}
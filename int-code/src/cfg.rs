@@ -0,0 +1,559 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use crate::instruction::Instruction;
+use crate::int_code::{Mode, OpCode};
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum EdgeKind {
+    Fallthrough,
+    Jump,
+    Taken,
+    NotTaken,
+    Call,
+    CallReturn,
+}
+
+impl EdgeKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EdgeKind::Fallthrough => "fallthrough",
+            EdgeKind::Jump => "jump",
+            EdgeKind::Taken => "taken",
+            EdgeKind::NotTaken => "not taken",
+            EdgeKind::Call => "call",
+            EdgeKind::CallReturn => "call return",
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash, PartialOrd, Ord)]
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    pub kind: EdgeKind,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct BasicBlock {
+    pub start: usize,
+    pub instructions: Vec<Instruction>,
+}
+
+impl BasicBlock {
+    pub fn last(&self) -> &Instruction {
+        self.instructions
+            .last()
+            .expect("basic blocks contain at least one instruction")
+    }
+
+    pub fn end(&self) -> usize {
+        self.last().next_address()
+    }
+}
+
+/// an instruction writing into a cell which is also decoded as code
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct SelfModification {
+    pub writer: usize,
+    pub target: usize,
+    pub instruction: usize,
+}
+
+/// code reached by a call which sets up its own frame with `arb +n` and
+/// returns with `arb -n` followed by a jump through the relative base
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Subroutine {
+    pub entry: usize,
+    pub frame_size: i64,
+    pub returns: Vec<usize>,
+}
+
+/// static control-flow graph of an int code image, built by recursive
+/// traversal from address 0
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct ControlFlowGraph {
+    blocks: BTreeMap<usize, BasicBlock>,
+    edges: Vec<Edge>,
+    computed_jumps: Vec<usize>,
+    invalid: Vec<usize>,
+    self_modifications: Vec<SelfModification>,
+    subroutines: Vec<Subroutine>,
+}
+
+impl ControlFlowGraph {
+    pub fn build(memory: &[i64]) -> Self {
+        let mut instructions: BTreeMap<usize, Instruction> = BTreeMap::new();
+        let mut successors: BTreeMap<usize, Vec<(usize, EdgeKind)>> = BTreeMap::new();
+        let mut computed_jumps: BTreeSet<usize> = BTreeSet::new();
+        let mut invalid: BTreeSet<usize> = BTreeSet::new();
+
+        let mut todo: Vec<usize> = vec![0];
+
+        while let Some(address) = todo.pop() {
+            if instructions.contains_key(&address) || invalid.contains(&address) {
+                continue;
+            }
+
+            let instruction = match Instruction::decode(memory, address) {
+                Some(i) => i,
+                None => {
+                    invalid.insert(address);
+                    continue;
+                }
+            };
+
+            let next = ControlFlowGraph::instruction_successors(memory, &instruction);
+
+            if instruction.is_jump() && instruction.jump_target().is_none() {
+                computed_jumps.insert(address);
+            }
+
+            todo.extend(next.iter().map(|(to, _)| *to));
+            successors.insert(address, next);
+            instructions.insert(address, instruction);
+        }
+
+        let blocks = ControlFlowGraph::split_blocks(&instructions, &successors);
+        let edges = ControlFlowGraph::block_edges(&blocks, &successors);
+        let self_modifications = ControlFlowGraph::find_self_modifications(&instructions);
+
+        let mut cfg = ControlFlowGraph {
+            blocks,
+            edges,
+            computed_jumps: computed_jumps.into_iter().collect(),
+            invalid: invalid.into_iter().collect(),
+            self_modifications,
+            subroutines: Vec::new(),
+        };
+
+        cfg.subroutines = cfg.find_subroutines();
+
+        cfg
+    }
+
+    fn instruction_successors(memory: &[i64], instruction: &Instruction) -> Vec<(usize, EdgeKind)> {
+        let next_address = instruction.next_address();
+
+        if !instruction.is_jump() {
+            return match instruction.op_code {
+                OpCode::End => Vec::new(),
+                _ => vec![(next_address, EdgeKind::Fallthrough)],
+            };
+        }
+
+        let target = instruction.jump_target();
+
+        match instruction.constant_condition() {
+            Some(false) => vec![(next_address, EdgeKind::NotTaken)],
+            Some(true) => match target {
+                Some(t) if ControlFlowGraph::is_call(memory, instruction) => {
+                    vec![(t, EdgeKind::Call), (next_address, EdgeKind::CallReturn)]
+                }
+                Some(t) => vec![(t, EdgeKind::Jump)],
+                None => Vec::new(),
+            },
+            None => {
+                let mut next = vec![(next_address, EdgeKind::NotTaken)];
+
+                if let Some(t) = target {
+                    next.push((t, EdgeKind::Taken));
+                }
+
+                next
+            }
+        }
+    }
+
+    // a call stores the address following the jump right before jumping, the
+    // store is decoded from memory so the result doesn't depend on the order
+    // instructions are visited in
+    fn is_call(memory: &[i64], jump: &Instruction) -> bool {
+        (1..=4)
+            .filter_map(|length| jump.address.checked_sub(length))
+            .filter_map(|address| Instruction::decode(memory, address))
+            .filter(|previous| previous.next_address() == jump.address)
+            .filter_map(|previous| previous.constant_store())
+            .any(|stored| stored == jump.next_address() as i64)
+    }
+
+    fn split_blocks(
+        instructions: &BTreeMap<usize, Instruction>,
+        successors: &BTreeMap<usize, Vec<(usize, EdgeKind)>>,
+    ) -> BTreeMap<usize, BasicBlock> {
+        let mut leaders: BTreeSet<usize> = BTreeSet::new();
+        leaders.insert(0);
+
+        successors.values().for_each(|next| {
+            let plain_fallthrough = next.len() == 1 && next[0].1 == EdgeKind::Fallthrough;
+
+            if !plain_fallthrough {
+                leaders.extend(next.iter().map(|(to, _)| *to));
+            }
+        });
+
+        let mut blocks: BTreeMap<usize, BasicBlock> = BTreeMap::new();
+
+        for leader in leaders.iter().filter(|l| instructions.contains_key(l)) {
+            let mut block_instructions: Vec<Instruction> = Vec::new();
+            let mut address = *leader;
+
+            while let Some(instruction) = instructions.get(&address) {
+                block_instructions.push(instruction.clone());
+
+                let next = &successors[&address];
+                let plain_fallthrough = next.len() == 1 && next[0].1 == EdgeKind::Fallthrough;
+
+                address = instruction.next_address();
+
+                if !plain_fallthrough || leaders.contains(&address) {
+                    break;
+                }
+            }
+
+            blocks.insert(
+                *leader,
+                BasicBlock {
+                    start: *leader,
+                    instructions: block_instructions,
+                },
+            );
+        }
+
+        blocks
+    }
+
+    fn block_edges(
+        blocks: &BTreeMap<usize, BasicBlock>,
+        successors: &BTreeMap<usize, Vec<(usize, EdgeKind)>>,
+    ) -> Vec<Edge> {
+        blocks
+            .values()
+            .flat_map(|block| {
+                successors[&block.last().address]
+                    .iter()
+                    .filter(|(to, _)| blocks.contains_key(to))
+                    .map(move |(to, kind)| Edge {
+                        from: block.start,
+                        to: *to,
+                        kind: *kind,
+                    })
+            })
+            .collect()
+    }
+
+    fn find_self_modifications(
+        instructions: &BTreeMap<usize, Instruction>,
+    ) -> Vec<SelfModification> {
+        instructions
+            .values()
+            .filter_map(|writer| {
+                let target = writer.write_parameter()?.static_address()?;
+
+                instructions
+                    .range(..=target)
+                    .next_back()
+                    .map(|(_, i)| i)
+                    .filter(|i| i.contains(target))
+                    .map(|i| SelfModification {
+                        writer: writer.address,
+                        target,
+                        instruction: i.address,
+                    })
+            })
+            .collect()
+    }
+
+    fn find_subroutines(&self) -> Vec<Subroutine> {
+        let mut entries: BTreeSet<usize> = self
+            .edges
+            .iter()
+            .filter(|e| e.kind == EdgeKind::Call)
+            .map(|e| e.to)
+            .collect();
+
+        entries.extend(
+            self.blocks
+                .values()
+                .filter(|b| b.start != 0)
+                .filter(|b| {
+                    b.instructions[0]
+                        .relative_base_adjustment()
+                        .is_some_and(|n| n > 0)
+                })
+                .map(|b| b.start),
+        );
+
+        entries
+            .into_iter()
+            .filter_map(|entry| {
+                let frame_size = self.blocks[&entry].instructions[0].relative_base_adjustment()?;
+
+                if frame_size <= 0 {
+                    return None;
+                }
+
+                let returns = self.find_returns(entry, frame_size);
+
+                if returns.is_empty() {
+                    return None;
+                }
+
+                Some(Subroutine {
+                    entry,
+                    frame_size,
+                    returns,
+                })
+            })
+            .collect()
+    }
+
+    // walks the body of a subroutine (stepping over nested calls) and collects
+    // the addresses of `arb -frame_size` + relative jump pairs
+    fn find_returns(&self, entry: usize, frame_size: i64) -> Vec<usize> {
+        let mut returns: Vec<usize> = Vec::new();
+        let mut seen: HashSet<usize> = HashSet::new();
+        let mut todo: Vec<usize> = vec![entry];
+
+        while let Some(start) = todo.pop() {
+            if !seen.insert(start) {
+                continue;
+            }
+
+            let block = &self.blocks[&start];
+
+            block.instructions.windows(2).for_each(|pair| {
+                let pops_frame = pair[0].relative_base_adjustment() == Some(-frame_size);
+                let jumps_relative =
+                    pair[1].is_jump() && pair[1].parameters[1].mode == Mode::Relative;
+
+                if pops_frame && jumps_relative {
+                    returns.push(pair[1].address);
+                }
+            });
+
+            todo.extend(
+                self.edges
+                    .iter()
+                    .filter(|e| e.from == start && e.kind != EdgeKind::Call)
+                    .map(|e| e.to),
+            );
+        }
+
+        returns.sort();
+        returns
+    }
+
+    pub fn blocks(&self) -> &BTreeMap<usize, BasicBlock> {
+        &self.blocks
+    }
+
+    pub fn edges(&self) -> &Vec<Edge> {
+        &self.edges
+    }
+
+    pub fn successors(&self, block: usize) -> Vec<Edge> {
+        self.edges
+            .iter()
+            .filter(|e| e.from == block)
+            .copied()
+            .collect()
+    }
+
+    /// decoded instruction covering `address`, if it is part of reachable code
+    pub fn instruction_at(&self, address: usize) -> Option<&Instruction> {
+        self.blocks
+            .range(..=address)
+            .next_back()
+            .and_then(|(_, b)| b.instructions.iter().find(|i| i.contains(address)))
+    }
+
+    /// jumps whose target is read from memory and therefore unknown statically
+    pub fn computed_jumps(&self) -> &Vec<usize> {
+        &self.computed_jumps
+    }
+
+    /// reached addresses which do not hold a valid instruction
+    pub fn invalid(&self) -> &Vec<usize> {
+        &self.invalid
+    }
+
+    pub fn self_modifications(&self) -> &Vec<SelfModification> {
+        &self.self_modifications
+    }
+
+    pub fn subroutines(&self) -> &Vec<Subroutine> {
+        &self.subroutines
+    }
+
+    pub fn to_dot(&self) -> String {
+        let modified: HashSet<usize> = self
+            .self_modifications
+            .iter()
+            .map(|m| m.instruction)
+            .collect();
+        let entries: HashSet<usize> = self.subroutines.iter().map(|s| s.entry).collect();
+
+        let mut lines: Vec<String> = vec![
+            "digraph cfg {".to_owned(),
+            "    node [shape=box, fontname=\"monospace\"];".to_owned(),
+        ];
+
+        for block in self.blocks.values() {
+            let mut label: String = block
+                .instructions
+                .iter()
+                .map(|i| format!("{}: {}\\l", i.address, i))
+                .collect();
+
+            if entries.contains(&block.start) {
+                label = format!("sub_{}:\\l{}", block.start, label);
+            }
+
+            let mut attributes = vec![format!("label=\"{}\"", label)];
+
+            if block
+                .instructions
+                .iter()
+                .any(|i| modified.contains(&i.address))
+            {
+                attributes.push("style=filled, fillcolor=orange".to_owned());
+            }
+
+            if self.computed_jumps.contains(&block.last().address) {
+                attributes.push("color=red".to_owned());
+            }
+
+            lines.push(format!("    b{} [{}];", block.start, attributes.join(", ")));
+        }
+
+        for edge in self.edges.iter() {
+            let style = match edge.kind {
+                EdgeKind::Call | EdgeKind::CallReturn => ", style=dashed",
+                _ => "",
+            };
+
+            lines.push(format!(
+                "    b{} -> b{} [label=\"{}\"{}];",
+                edge.from,
+                edge.to,
+                edge.kind.as_str(),
+                style
+            ));
+        }
+
+        lines.push("}".to_owned());
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn build_branch_correct_blocks_and_edges() {
+        // in [12]; jz [12], 8; out 1; halt; out 0; halt
+        let memory: &[i64] = &[3, 12, 1006, 12, 8, 104, 1, 99, 104, 0, 99, 0, 0];
+
+        let cfg = ControlFlowGraph::build(memory);
+
+        assert_eq!(
+            cfg.blocks().keys().copied().collect::<Vec<_>>(),
+            vec![0, 5, 8]
+        );
+        assert_eq!(
+            cfg.edges(),
+            &vec![
+                Edge {
+                    from: 0,
+                    to: 5,
+                    kind: EdgeKind::NotTaken
+                },
+                Edge {
+                    from: 0,
+                    to: 8,
+                    kind: EdgeKind::Taken
+                },
+            ]
+        );
+        assert!(cfg.computed_jumps().is_empty());
+        assert!(cfg.self_modifications().is_empty());
+    }
+
+    #[test]
+    fn build_computed_jump_and_self_modification_flagged() {
+        // add 1, 0, [7]; jnz [9], [9]; halt (patched by the add)
+        let memory: &[i64] = &[1101, 1, 0, 7, 5, 9, 9, 99, 0, 7];
+
+        let cfg = ControlFlowGraph::build(memory);
+
+        assert_eq!(cfg.computed_jumps(), &vec![4]);
+        assert_eq!(
+            cfg.self_modifications(),
+            &vec![SelfModification {
+                writer: 0,
+                target: 7,
+                instruction: 7
+            }]
+        );
+    }
+
+    #[test]
+    fn build_subroutine_detected() {
+        let memory: &[i64] = &[
+            109, 100, // arb 100
+            21101, 9, 0, 0, // store return address 9 at [rb+0]
+            1105, 1, 10, // call 10
+            99, // halt
+            109, 1, // arb 1
+            104, 42, // out 42
+            109, -1, // arb -1
+            2105, 1, 0, // return
+        ];
+
+        let cfg = ControlFlowGraph::build(memory);
+
+        assert_eq!(
+            cfg.subroutines(),
+            &vec![Subroutine {
+                entry: 10,
+                frame_size: 1,
+                returns: vec![16]
+            }]
+        );
+        assert!(cfg.edges().contains(&Edge {
+            from: 0,
+            to: 9,
+            kind: EdgeKind::CallReturn
+        }));
+
+        let dot = cfg.to_dot();
+        assert!(dot.starts_with("digraph cfg {"));
+        assert!(dot.contains("b0 -> b10 [label=\"call\", style=dashed];"));
+    }
+
+    #[test]
+    fn build_call_reached_before_its_store_detected() {
+        let memory: &[i64] = &[
+            1005, 100, 7, // jnz [100], 7
+            1101, 0, 10, 200, // store return address 10 at [200]
+            1105, 1, 11, // call 11
+            99, // halt
+            99, // halt
+        ];
+
+        let cfg = ControlFlowGraph::build(memory);
+
+        assert!(cfg.edges().contains(&Edge {
+            from: 7,
+            to: 11,
+            kind: EdgeKind::Call
+        }));
+        assert!(cfg.edges().contains(&Edge {
+            from: 7,
+            to: 10,
+            kind: EdgeKind::CallReturn
+        }));
+    }
+}
@@ -0,0 +1,215 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::cfg::ControlFlowGraph;
+use crate::instruction::Instruction;
+
+/// executed instructions and branch outcomes of one or more runs
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct Coverage {
+    executed: BTreeMap<usize, usize>,
+    branches: BTreeMap<usize, (usize, usize)>,
+}
+
+impl Coverage {
+    pub fn new() -> Self {
+        Coverage::default()
+    }
+
+    pub fn record_instruction(&mut self, address: usize) {
+        *self.executed.entry(address).or_default() += 1;
+    }
+
+    pub fn record_branch(&mut self, address: usize, taken: bool) {
+        let (t, n) = self.branches.entry(address).or_default();
+
+        if taken {
+            *t += 1;
+        } else {
+            *n += 1;
+        }
+    }
+
+    /// adds the counts of another run
+    pub fn merge(&mut self, other: &Coverage) {
+        other
+            .executed
+            .iter()
+            .for_each(|(a, c)| *self.executed.entry(*a).or_default() += c);
+
+        other.branches.iter().for_each(|(a, (t, n))| {
+            let (st, sn) = self.branches.entry(*a).or_default();
+            *st += t;
+            *sn += n;
+        });
+    }
+
+    /// address -> number of executions
+    pub fn executed(&self) -> &BTreeMap<usize, usize> {
+        &self.executed
+    }
+
+    /// address -> (taken, not taken)
+    pub fn branches(&self) -> &BTreeMap<usize, (usize, usize)> {
+        &self.branches
+    }
+
+    fn branch_marker(&self, instruction: &Instruction) -> String {
+        if !instruction.is_jump() || instruction.constant_condition().is_some() {
+            return String::new();
+        }
+
+        match self.branches.get(&instruction.address) {
+            Some((t, n)) => {
+                let partial = if *t == 0 || *n == 0 { " !" } else { "" };
+                format!("  ; taken {} / not taken {}{}", t, n, partial)
+            }
+            None => "  ; never reached !".to_owned(),
+        }
+    }
+
+    /// disassembly listing of `memory` with execution counts, branch outcomes
+    /// and a summary; cells which are neither reachable code nor executed are
+    /// shown as data
+    pub fn report(&self, memory: &[i64]) -> String {
+        let cfg = ControlFlowGraph::build(memory);
+
+        let mut starts: BTreeSet<usize> = self.executed.keys().copied().collect();
+        cfg.blocks().values().for_each(|b| {
+            starts.extend(b.instructions.iter().map(|i| i.address));
+        });
+
+        let mut lines: Vec<String> = Vec::new();
+        let mut data: Vec<i64> = Vec::new();
+        let mut data_start = 0;
+        let mut code_total = 0;
+        let mut branch_total = 0;
+        let mut branch_covered = 0;
+
+        let mut address = 0;
+        while address < memory.len() {
+            let instruction = if starts.contains(&address) {
+                Instruction::decode(memory, address)
+            } else {
+                None
+            };
+
+            let instruction = match instruction {
+                Some(i) => i,
+                None => {
+                    if data.is_empty() {
+                        data_start = address;
+                    }
+                    data.push(memory[address]);
+                    address += 1;
+
+                    if data.len() == 8 || starts.contains(&address) || address == memory.len() {
+                        lines.push(Coverage::data_line(data_start, &data));
+                        data.clear();
+                    }
+
+                    continue;
+                }
+            };
+
+            if !data.is_empty() {
+                lines.push(Coverage::data_line(data_start, &data));
+                data.clear();
+            }
+
+            code_total += 1;
+
+            if instruction.is_jump() && instruction.constant_condition().is_none() {
+                branch_total += 1;
+
+                if let Some((t, n)) = self.branches.get(&address) {
+                    if *t > 0 && *n > 0 {
+                        branch_covered += 1;
+                    }
+                }
+            }
+
+            let count = match self.executed.get(&address) {
+                Some(c) => c.to_string(),
+                None => "-".to_owned(),
+            };
+
+            lines.push(format!(
+                "{:>8} | {:>5}: {}{}",
+                count,
+                address,
+                instruction,
+                self.branch_marker(&instruction)
+            ));
+
+            address = instruction.next_address();
+        }
+
+        let executed = self.executed.keys().filter(|a| **a < memory.len()).count();
+
+        let mut report = vec![
+            format!("instructions executed: {}/{}", executed, code_total),
+            format!(
+                "branches fully covered: {}/{}",
+                branch_covered, branch_total
+            ),
+            String::new(),
+        ];
+        report.extend(lines);
+
+        report.join("\n")
+    }
+
+    fn data_line(start: usize, data: &[i64]) -> String {
+        let values: Vec<String> = data.iter().map(|v| v.to_string()).collect();
+        format!("{:>8} | {:>5}: data {}", "", start, values.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::int_code::IntCode;
+
+    // in [12]; jz [12], 8; out 1; halt; out 0; halt
+    fn branch_program() -> Vec<i64> {
+        vec![3, 12, 1006, 12, 8, 104, 1, 99, 104, 0, 99, 0, 0]
+    }
+
+    fn run(input: i64) -> Coverage {
+        let mut int_code = IntCode::new(branch_program(), Some(input));
+        int_code.set_coverage(true);
+        int_code.run().unwrap();
+        int_code.get_coverage().clone()
+    }
+
+    #[test]
+    fn coverage_single_run_correct_output() {
+        let coverage = run(1);
+
+        assert_eq!(
+            coverage.executed().keys().copied().collect::<Vec<_>>(),
+            vec![0, 2, 5, 7]
+        );
+        assert_eq!(coverage.branches().get(&2), Some(&(0, 1)));
+    }
+
+    #[test]
+    fn coverage_merged_report_correct_output() {
+        let mut coverage = run(1);
+        coverage.merge(&run(0));
+        coverage.merge(&run(5));
+
+        assert_eq!(coverage.branches().get(&2), Some(&(1, 2)));
+
+        let report = coverage.report(&branch_program());
+        let lines: Vec<&str> = report.lines().collect();
+
+        assert_eq!(lines[0], "instructions executed: 6/6");
+        assert_eq!(lines[1], "branches fully covered: 1/1");
+        assert_eq!(
+            lines[4],
+            "       3 |     2: jz [12], 8  ; taken 1 / not taken 2"
+        );
+        assert_eq!(lines[9], "         |    11: data 0, 0");
+    }
+}
@@ -0,0 +1,922 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::cfg::{ControlFlowGraph, Edge, EdgeKind, Subroutine};
+use crate::instruction::{Instruction, Parameter};
+use crate::int_code::{Mode, OpCode};
+
+/// a function of the decompiled program: `main` at address 0 or a subroutine
+/// following the relative base calling convention
+struct Function {
+    name: String,
+    entry: usize,
+    frame_size: Option<i64>,
+    blocks: BTreeSet<usize>,
+    successors: HashMap<usize, Vec<Edge>>,
+    ipdom: HashMap<usize, Option<usize>>,
+    loops: HashMap<usize, Loop>,
+}
+
+struct Loop {
+    body: HashSet<usize>,
+    exit: Option<usize>,
+}
+
+/// lifts the control-flow graph of an int code image into structured pseudo-code
+pub struct Decompiler<'a> {
+    memory: &'a [i64],
+    cfg: ControlFlowGraph,
+    names: HashMap<usize, String>,
+}
+
+impl<'a> Decompiler<'a> {
+    pub fn new(memory: &'a [i64]) -> Self {
+        Decompiler {
+            memory,
+            cfg: ControlFlowGraph::build(memory),
+            names: HashMap::new(),
+        }
+    }
+
+    /// use `name` instead of the generated `v<address>` for a memory cell
+    pub fn name_cell(&mut self, address: usize, name: &str) {
+        self.names.insert(address, name.to_owned());
+    }
+
+    pub fn decompile(&self) -> String {
+        let mut functions = vec![self.function("main".to_owned(), 0, None)];
+
+        functions.extend(
+            self.cfg
+                .subroutines()
+                .iter()
+                .map(|s| self.function(format!("sub_{}", s.entry), s.entry, Some(s.frame_size))),
+        );
+
+        let mut sections: Vec<String> = Vec::new();
+
+        let globals = self.globals(&functions);
+        if !globals.is_empty() {
+            sections.push(globals.join("\n"));
+        }
+
+        sections.extend(functions.iter().map(|f| self.emit_function(f)));
+
+        sections.join("\n\n") + "\n"
+    }
+
+    fn function(&self, name: String, entry: usize, frame_size: Option<i64>) -> Function {
+        let mut blocks: BTreeSet<usize> = BTreeSet::new();
+        let mut successors: HashMap<usize, Vec<Edge>> = HashMap::new();
+        let mut todo: Vec<usize> = vec![entry];
+
+        while let Some(block) = todo.pop() {
+            if !blocks.insert(block) {
+                continue;
+            }
+
+            let next: Vec<Edge> = self
+                .cfg
+                .successors(block)
+                .into_iter()
+                .filter(|e| e.kind != EdgeKind::Call)
+                .collect();
+
+            todo.extend(next.iter().map(|e| e.to));
+            successors.insert(block, next);
+        }
+
+        let dominators = Decompiler::dominators(&blocks, entry, &successors);
+        let ipdom = Decompiler::immediate_post_dominators(&blocks, &successors);
+        let loops = Decompiler::natural_loops(&blocks, &successors, &dominators);
+
+        Function {
+            name,
+            entry,
+            frame_size,
+            blocks,
+            successors,
+            ipdom,
+            loops,
+        }
+    }
+
+    fn predecessors(
+        blocks: &BTreeSet<usize>,
+        successors: &HashMap<usize, Vec<Edge>>,
+    ) -> HashMap<usize, Vec<usize>> {
+        let mut predecessors: HashMap<usize, Vec<usize>> =
+            blocks.iter().map(|b| (*b, Vec::new())).collect();
+
+        successors.values().flatten().for_each(|e| {
+            predecessors.entry(e.to).or_default().push(e.from);
+        });
+
+        predecessors
+    }
+
+    fn dominators(
+        blocks: &BTreeSet<usize>,
+        entry: usize,
+        successors: &HashMap<usize, Vec<Edge>>,
+    ) -> HashMap<usize, HashSet<usize>> {
+        let predecessors = Decompiler::predecessors(blocks, successors);
+        let all: HashSet<usize> = blocks.iter().copied().collect();
+
+        let mut dominators: HashMap<usize, HashSet<usize>> = blocks
+            .iter()
+            .map(|b| {
+                if *b == entry {
+                    (*b, [entry].iter().copied().collect())
+                } else {
+                    (*b, all.clone())
+                }
+            })
+            .collect();
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+
+            for block in blocks.iter().filter(|b| **b != entry) {
+                let mut next: HashSet<usize> = predecessors[block]
+                    .iter()
+                    .map(|p| dominators[p].clone())
+                    .fold(None, |acc: Option<HashSet<usize>>, d| match acc {
+                        None => Some(d),
+                        Some(a) => Some(a.intersection(&d).copied().collect()),
+                    })
+                    .unwrap_or_default();
+                next.insert(*block);
+
+                if next != dominators[block] {
+                    dominators.insert(*block, next);
+                    changed = true;
+                }
+            }
+        }
+
+        dominators
+    }
+
+    // post dominators with a virtual exit after every block without successors
+    fn immediate_post_dominators(
+        blocks: &BTreeSet<usize>,
+        successors: &HashMap<usize, Vec<Edge>>,
+    ) -> HashMap<usize, Option<usize>> {
+        let all: HashSet<usize> = blocks.iter().copied().collect();
+
+        let mut post_dominators: HashMap<usize, HashSet<usize>> = blocks
+            .iter()
+            .map(|b| {
+                if successors[b].is_empty() {
+                    (*b, [*b].iter().copied().collect())
+                } else {
+                    (*b, all.clone())
+                }
+            })
+            .collect();
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+
+            for block in blocks.iter().rev().filter(|b| !successors[b].is_empty()) {
+                let mut next: HashSet<usize> = successors[block]
+                    .iter()
+                    .map(|e| post_dominators[&e.to].clone())
+                    .fold(None, |acc: Option<HashSet<usize>>, d| match acc {
+                        None => Some(d),
+                        Some(a) => Some(a.intersection(&d).copied().collect()),
+                    })
+                    .unwrap_or_default();
+                next.insert(*block);
+
+                if next != post_dominators[block] {
+                    post_dominators.insert(*block, next);
+                    changed = true;
+                }
+            }
+        }
+
+        // the immediate post dominator is the strict post dominator which is
+        // post dominated by all the others
+        blocks
+            .iter()
+            .map(|b| {
+                let strict: Vec<usize> = post_dominators[b]
+                    .iter()
+                    .copied()
+                    .filter(|p| p != b)
+                    .collect();
+
+                let ipdom = strict.iter().copied().find(|candidate| {
+                    strict
+                        .iter()
+                        .all(|p| post_dominators[candidate].contains(p))
+                });
+
+                (*b, ipdom)
+            })
+            .collect()
+    }
+
+    fn natural_loops(
+        blocks: &BTreeSet<usize>,
+        successors: &HashMap<usize, Vec<Edge>>,
+        dominators: &HashMap<usize, HashSet<usize>>,
+    ) -> HashMap<usize, Loop> {
+        let predecessors = Decompiler::predecessors(blocks, successors);
+        let mut loops: HashMap<usize, Loop> = HashMap::new();
+
+        let back_edges = successors
+            .values()
+            .flatten()
+            .filter(|e| dominators[&e.from].contains(&e.to));
+
+        for edge in back_edges {
+            let header = edge.to;
+            let body = &mut loops
+                .entry(header)
+                .or_insert_with(|| Loop {
+                    body: [header].iter().copied().collect(),
+                    exit: None,
+                })
+                .body;
+
+            let mut todo: Vec<usize> = vec![edge.from];
+            while let Some(block) = todo.pop() {
+                if body.insert(block) {
+                    todo.extend(predecessors[&block].iter().copied());
+                }
+            }
+        }
+
+        for (header, l) in loops.iter_mut() {
+            let exits: BTreeSet<usize> = l
+                .body
+                .iter()
+                .flat_map(|b| successors[b].iter().map(|e| e.to))
+                .filter(|to| !l.body.contains(to))
+                .collect();
+
+            // prefer leaving the loop right from the header (`while` loops)
+            let header_exit = successors[header]
+                .iter()
+                .map(|e| e.to)
+                .find(|to| exits.contains(to));
+
+            l.exit = header_exit.or_else(|| exits.iter().next().copied());
+        }
+
+        loops
+    }
+
+    fn globals(&self, functions: &[Function]) -> Vec<String> {
+        let mut cells: BTreeSet<usize> = BTreeSet::new();
+
+        for function in functions {
+            for block in function.blocks.iter() {
+                self.cfg.blocks()[block].instructions.iter().for_each(|i| {
+                    cells.extend(i.parameters.iter().filter_map(|p| p.static_address()))
+                });
+            }
+        }
+
+        cells
+            .into_iter()
+            .filter(|c| self.cfg.instruction_at(*c).is_none())
+            .map(|c| {
+                format!(
+                    "var {} = {};",
+                    self.cell_name(c),
+                    self.memory.get(c).copied().unwrap_or(0)
+                )
+            })
+            .collect()
+    }
+
+    fn cell_name(&self, address: usize) -> String {
+        if let Some(name) = self.names.get(&address) {
+            return name.clone();
+        }
+
+        if self.cfg.instruction_at(address).is_some() {
+            return format!("code[{}]", address);
+        }
+
+        format!("v{}", address)
+    }
+
+    fn operand(&self, function: &Function, parameter: Parameter) -> String {
+        match parameter.mode {
+            Mode::Immediate => parameter.value.to_string(),
+            Mode::Position => match parameter.static_address() {
+                Some(address) => self.cell_name(address),
+                None => format!("mem[{}]", parameter.value),
+            },
+            Mode::Relative => match function.frame_size {
+                Some(n) if parameter.value == -n => "ret_addr".to_owned(),
+                Some(n) if parameter.value > -n && parameter.value < 0 => {
+                    format!("arg{}", parameter.value + n)
+                }
+                _ => format!("rb[{}]", parameter.value),
+            },
+        }
+    }
+
+    fn subroutine(&self, entry: usize) -> Option<&Subroutine> {
+        self.cfg.subroutines().iter().find(|s| s.entry == entry)
+    }
+
+    // addresses of instructions which are absorbed by a higher level construct
+    // (frame setup, return sequence, call setup, folded comparisons)
+    fn hidden_instructions(&self, function: &Function, block: usize) -> HashSet<usize> {
+        let instructions = &self.cfg.blocks()[&block].instructions;
+        let mut hidden: HashSet<usize> = HashSet::new();
+
+        if let Some(n) = function.frame_size {
+            if block == function.entry {
+                hidden.insert(instructions[0].address);
+            }
+
+            instructions.windows(2).for_each(|pair| {
+                if pair[0].relative_base_adjustment() == Some(-n) && self.is_return(&pair[1]) {
+                    hidden.insert(pair[0].address);
+                }
+            });
+        }
+
+        if let Some(callee) = self.called_subroutine(block) {
+            let call = &instructions[instructions.len() - 1];
+
+            // the return address store may live in the block before when the
+            // call itself is a jump target, it is shown as a statement then
+            if let Some(store) = Decompiler::return_address_store(instructions) {
+                hidden.insert(store.address);
+            }
+
+            instructions
+                .iter()
+                .filter(|i| i.address != call.address)
+                .filter(|i| Decompiler::argument_slot(i, callee.frame_size).is_some())
+                .for_each(|i| {
+                    hidden.insert(i.address);
+                });
+        }
+
+        if self.folded_comparison(block).is_some() {
+            hidden.insert(instructions[instructions.len() - 2].address);
+        }
+
+        hidden
+    }
+
+    fn is_return(&self, instruction: &Instruction) -> bool {
+        self.cfg
+            .subroutines()
+            .iter()
+            .any(|s| s.returns.contains(&instruction.address))
+    }
+
+    // instruction right before the call storing the address it returns to
+    fn return_address_store(instructions: &[Instruction]) -> Option<&Instruction> {
+        match instructions {
+            [.., store, call] if store.constant_store() == Some(call.next_address() as i64) => {
+                Some(store)
+            }
+            _ => None,
+        }
+    }
+
+    fn called_subroutine(&self, block: usize) -> Option<&Subroutine> {
+        self.cfg
+            .successors(block)
+            .iter()
+            .find(|e| e.kind == EdgeKind::Call)
+            .and_then(|e| self.subroutine(e.to))
+    }
+
+    fn argument_slot(instruction: &Instruction, frame_size: i64) -> Option<i64> {
+        let target = instruction.write_parameter()?;
+
+        match (&instruction.op_code, target.mode) {
+            (OpCode::Add { .. }, Mode::Relative) | (OpCode::Mut { .. }, Mode::Relative)
+                if target.value > 0 && target.value < frame_size =>
+            {
+                Some(target.value)
+            }
+            _ => None,
+        }
+    }
+
+    // comparison right in front of a conditional jump testing its result
+    fn folded_comparison(&self, block: usize) -> Option<&Instruction> {
+        let instructions = &self.cfg.blocks()[&block].instructions;
+
+        if instructions.len() < 2 {
+            return None;
+        }
+
+        let jump = &instructions[instructions.len() - 1];
+        let comparison = &instructions[instructions.len() - 2];
+
+        if !jump.is_jump() || jump.constant_condition().is_some() {
+            return None;
+        }
+
+        match comparison.op_code {
+            OpCode::LessThan { .. } | OpCode::Equals { .. }
+                if comparison.write_parameter() == Some(jump.parameters[0]) =>
+            {
+                Some(comparison)
+            }
+            _ => None,
+        }
+    }
+
+    fn expression(&self, function: &Function, instruction: &Instruction) -> String {
+        let p = &instruction.parameters;
+
+        match instruction.op_code {
+            OpCode::Add { .. } if p[0].mode == Mode::Immediate && p[0].value == 0 => {
+                self.operand(function, p[1])
+            }
+            OpCode::Add { .. } if p[1].mode == Mode::Immediate && p[1].value == 0 => {
+                self.operand(function, p[0])
+            }
+            OpCode::Mut { .. } if p[0].mode == Mode::Immediate && p[0].value == 1 => {
+                self.operand(function, p[1])
+            }
+            OpCode::Mut { .. } if p[1].mode == Mode::Immediate && p[1].value == 1 => {
+                self.operand(function, p[0])
+            }
+            OpCode::Add { .. } => format!(
+                "{} + {}",
+                self.operand(function, p[0]),
+                self.operand(function, p[1])
+            ),
+            OpCode::Mut { .. } => format!(
+                "{} * {}",
+                self.operand(function, p[0]),
+                self.operand(function, p[1])
+            ),
+            OpCode::LessThan { .. } => format!(
+                "{} < {}",
+                self.operand(function, p[0]),
+                self.operand(function, p[1])
+            ),
+            OpCode::Equals { .. } => format!(
+                "{} == {}",
+                self.operand(function, p[0]),
+                self.operand(function, p[1])
+            ),
+            _ => String::new(),
+        }
+    }
+
+    fn statement(&self, function: &Function, instruction: &Instruction) -> Option<String> {
+        let p = &instruction.parameters;
+
+        let statement = match instruction.op_code {
+            OpCode::Add { .. }
+            | OpCode::Mut { .. }
+            | OpCode::LessThan { .. }
+            | OpCode::Equals { .. } => format!(
+                "{} = {};",
+                self.operand(function, p[2]),
+                self.expression(function, instruction)
+            ),
+            OpCode::Input { .. } => format!("{} = input();", self.operand(function, p[0])),
+            OpCode::Output { .. } => format!("output({});", self.operand(function, p[0])),
+            OpCode::AdjustRelativeBase { .. } => {
+                format!("rb += {};", self.operand(function, p[0]))
+            }
+            OpCode::End => "halt;".to_owned(),
+            _ => return None,
+        };
+
+        Some(statement)
+    }
+
+    // (condition for taking the jump, condition for falling through)
+    fn conditions(&self, function: &Function, block: usize) -> (String, String) {
+        let jump = self.cfg.blocks()[&block].last();
+
+        let (non_zero, zero) = match self.folded_comparison(block) {
+            Some(comparison) => {
+                let (a, b) = (
+                    self.operand(function, comparison.parameters[0]),
+                    self.operand(function, comparison.parameters[1]),
+                );
+
+                match comparison.op_code {
+                    OpCode::LessThan { .. } => {
+                        (format!("{} < {}", a, b), format!("{} >= {}", a, b))
+                    }
+                    _ => (format!("{} == {}", a, b), format!("{} != {}", a, b)),
+                }
+            }
+            None => {
+                let value = self.operand(function, jump.parameters[0]);
+                (format!("{} != 0", value), format!("{} == 0", value))
+            }
+        };
+
+        match jump.op_code {
+            OpCode::JumpIfTrue { .. } => (non_zero, zero),
+            _ => (zero, non_zero),
+        }
+    }
+
+    fn emit_function(&self, function: &Function) -> String {
+        let arguments: Vec<String> = match function.frame_size {
+            Some(n) => (1..n).map(|i| format!("arg{}", i)).collect(),
+            None => Vec::new(),
+        };
+
+        let mut emitter = Emitter::new(self, function);
+        emitter.region(Some(function.entry), None, 1);
+
+        let mut lines = vec![format!("fn {}({}) {{", function.name, arguments.join(", "))];
+        lines.extend(emitter.finish());
+        lines.push("}".to_owned());
+
+        lines.join("\n")
+    }
+}
+
+/// emits the structured statements of a single function
+struct Emitter<'d, 'a> {
+    decompiler: &'d Decompiler<'a>,
+    function: &'d Function,
+    lines: Vec<(Option<usize>, String)>,
+    emitted: HashSet<usize>,
+    gotos: HashSet<usize>,
+    loops: Vec<(usize, Option<usize>)>,
+}
+
+impl<'d, 'a> Emitter<'d, 'a> {
+    fn new(decompiler: &'d Decompiler<'a>, function: &'d Function) -> Self {
+        Emitter {
+            decompiler,
+            function,
+            lines: Vec::new(),
+            emitted: HashSet::new(),
+            gotos: HashSet::new(),
+            loops: Vec::new(),
+        }
+    }
+
+    fn finish(self) -> Vec<String> {
+        let gotos = self.gotos;
+
+        self.lines
+            .into_iter()
+            .filter_map(|(label, line)| match label {
+                Some(l) if gotos.contains(&l) => Some(format!("L{}:", l)),
+                Some(_) => None,
+                None => Some(line),
+            })
+            .collect()
+    }
+
+    fn line(&mut self, depth: usize, line: String) {
+        self.lines
+            .push((None, format!("{}{}", "    ".repeat(depth), line)));
+    }
+
+    fn region(&mut self, start: Option<usize>, stop: Option<usize>, depth: usize) {
+        let mut current = start;
+
+        while let Some(block) = current {
+            if Some(block) == stop {
+                return;
+            }
+
+            if let Some((header, exit)) = self.loops.last().copied() {
+                if block == header {
+                    self.line(depth, "continue;".to_owned());
+                    return;
+                }
+
+                if Some(block) == exit {
+                    self.line(depth, "break;".to_owned());
+                    return;
+                }
+            }
+
+            if self.emitted.contains(&block) {
+                self.gotos.insert(block);
+                self.line(depth, format!("goto L{};", block));
+                return;
+            }
+
+            current = if self.function.loops.contains_key(&block) {
+                self.emit_loop(block, depth)
+            } else {
+                self.block(block, stop, depth)
+            };
+        }
+    }
+
+    fn emit_loop(&mut self, header: usize, depth: usize) -> Option<usize> {
+        let exit = self.function.loops[&header].exit;
+        let body = &self.function.loops[&header].body;
+
+        self.loops.push((header, exit));
+
+        let successors = &self.function.successors[&header];
+        let conditional = successors.len() == 2
+            && successors.iter().any(|e| Some(e.to) == exit)
+            && successors.iter().any(|e| body.contains(&e.to));
+
+        let hidden = self.decompiler.hidden_instructions(self.function, header);
+        let block = &self.decompiler.cfg.blocks()[&header];
+        let statements = block.instructions[..block.instructions.len() - 1]
+            .iter()
+            .filter(|i| !hidden.contains(&i.address))
+            .count();
+
+        self.emitted.insert(header);
+        self.lines.push((Some(header), String::new()));
+
+        if conditional && statements == 0 {
+            let (taken, not_taken) = self.decompiler.conditions(self.function, header);
+            let stay = successors.iter().find(|e| Some(e.to) != exit).unwrap();
+            let condition = if stay.kind == EdgeKind::Taken {
+                taken
+            } else {
+                not_taken
+            };
+
+            self.line(depth, format!("while ({}) {{", condition));
+            self.region(Some(stay.to), Some(header), depth + 1);
+        } else {
+            self.line(depth, "while (true) {".to_owned());
+            let next = self.block(header, Some(header), depth + 1);
+            self.region(next, Some(header), depth + 1);
+        }
+
+        self.line(depth, "}".to_owned());
+        self.loops.pop();
+
+        exit
+    }
+
+    fn jump_target_statement(&self, target: usize) -> Option<String> {
+        match self.loops.last() {
+            Some((header, _)) if *header == target => Some("continue;".to_owned()),
+            Some((_, exit)) if *exit == Some(target) => Some("break;".to_owned()),
+            _ => None,
+        }
+    }
+
+    // emits a single basic block and returns the block to continue with
+    fn block(&mut self, start: usize, stop: Option<usize>, depth: usize) -> Option<usize> {
+        let decompiler = self.decompiler;
+        let function = self.function;
+        let block = &decompiler.cfg.blocks()[&start];
+        let hidden = decompiler.hidden_instructions(function, start);
+
+        if !self.emitted.contains(&start) {
+            self.emitted.insert(start);
+            self.lines.push((Some(start), String::new()));
+        }
+
+        for instruction in block.instructions.iter() {
+            if hidden.contains(&instruction.address) {
+                continue;
+            }
+
+            if let Some(statement) = decompiler.statement(function, instruction) {
+                self.line(depth, statement);
+            }
+        }
+
+        let last = block.last();
+        let successors = function.successors[&start].clone();
+
+        if let Some(callee) = decompiler.called_subroutine(start) {
+            let mut arguments: Vec<String> = vec!["_".to_owned(); (callee.frame_size - 1) as usize];
+
+            block
+                .instructions
+                .iter()
+                .filter(|i| i.address != last.address)
+                .for_each(|i| {
+                    if let Some(slot) = Decompiler::argument_slot(i, callee.frame_size) {
+                        arguments[(slot - 1) as usize] = decompiler.expression(function, i);
+                    }
+                });
+
+            self.line(
+                depth,
+                format!("sub_{}({});", callee.entry, arguments.join(", ")),
+            );
+
+            return successors
+                .iter()
+                .find(|e| e.kind == EdgeKind::CallReturn)
+                .map(|e| e.to);
+        }
+
+        if !last.is_jump() {
+            return successors.first().map(|e| e.to);
+        }
+
+        if decompiler.is_return(last) {
+            self.line(depth, "return;".to_owned());
+            return None;
+        }
+
+        if last.jump_target().is_none() {
+            let target = decompiler.operand(function, last.parameters[1]);
+
+            if last.constant_condition() == Some(true) {
+                self.line(depth, format!("goto *{};", target));
+                return None;
+            }
+
+            let (taken, _) = decompiler.conditions(function, start);
+            self.line(depth, format!("if ({}) goto *{};", taken, target));
+
+            return successors.first().map(|e| e.to);
+        }
+
+        if successors.len() == 1 {
+            return Some(successors[0].to);
+        }
+
+        let edge = |kind: EdgeKind| successors.iter().find(|e| e.kind == kind).map(|e| e.to);
+
+        let (taken, not_taken) = match (edge(EdgeKind::Taken), edge(EdgeKind::NotTaken)) {
+            (Some(taken), Some(not_taken)) => (taken, not_taken),
+            _ => return successors.first().map(|e| e.to),
+        };
+        let (taken_condition, not_taken_condition) = decompiler.conditions(function, start);
+
+        // `if (..) break;` / `if (..) continue;`
+        if let Some(statement) = self.jump_target_statement(taken) {
+            self.line(depth, format!("if ({}) {}", taken_condition, statement));
+            return Some(not_taken);
+        }
+
+        if let Some(statement) = self.jump_target_statement(not_taken) {
+            self.line(depth, format!("if ({}) {}", not_taken_condition, statement));
+            return Some(taken);
+        }
+
+        let join = function.ipdom[&start].or(stop);
+
+        if Some(taken) == join {
+            self.line(depth, format!("if ({}) {{", not_taken_condition));
+            self.region(Some(not_taken), join, depth + 1);
+        } else if Some(not_taken) == join {
+            self.line(depth, format!("if ({}) {{", taken_condition));
+            self.region(Some(taken), join, depth + 1);
+        } else {
+            self.line(depth, format!("if ({}) {{", not_taken_condition));
+            self.region(Some(not_taken), join, depth + 1);
+            self.line(depth, "} else {".to_owned());
+            self.region(Some(taken), join, depth + 1);
+        }
+
+        self.line(depth, "}".to_owned());
+
+        join
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::loader;
+    use std::path::Path;
+
+    static BOOST_PATH: &str = "input/boost.txt";
+
+    #[test]
+    fn decompile_if_correct_output() {
+        // in [12]; jz [12], 8; out 1; halt; out 0; halt
+        let memory: &[i64] = &[3, 12, 1006, 12, 8, 104, 1, 99, 104, 0, 99, 0, 0];
+
+        let mut decompiler = Decompiler::new(memory);
+        decompiler.name_cell(12, "x");
+
+        assert_eq!(
+            decompiler.decompile(),
+            "var x = 0;\n\n\
+             fn main() {\n    \
+                 x = input();\n    \
+                 if (x != 0) {\n        \
+                     output(1);\n        \
+                     halt;\n    \
+                 } else {\n        \
+                     output(0);\n        \
+                     halt;\n    \
+                 }\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn decompile_while_correct_output() {
+        let memory: &[i64] = &[
+            1101, 0, 0, 21, // i = 0
+            1007, 21, 3, 22, // lt [21], 3, [22]
+            1006, 22, 20, // jz [22], 20
+            4, 21, // out [21]
+            101, 1, 21, 21, // i = i + 1
+            1105, 1, 4, // jnz 1, 4
+            99, 0, 0,
+        ];
+
+        let mut decompiler = Decompiler::new(memory);
+        decompiler.name_cell(21, "i");
+
+        let output = decompiler.decompile();
+
+        assert!(output.contains(
+            "    while (i < 3) {\n        \
+                 output(i);\n        \
+                 i = 1 + i;\n    \
+             }\n    \
+             halt;\n"
+        ));
+    }
+
+    #[test]
+    fn decompile_call_correct_output() {
+        let memory: &[i64] = &[
+            109, 100, // arb 100
+            21101, 0, 7, 1, // rb[1] = 7
+            21101, 13, 0, 0, // store return address
+            1105, 1, 14, // call 14
+            99, // halt
+            109, 2, // arb 2
+            204, -1, // out arg1
+            109, -2, // arb -2
+            2105, 1, 0, // return
+        ];
+
+        let output = Decompiler::new(memory).decompile();
+
+        assert_eq!(
+            output,
+            "fn main() {\n    \
+                 rb += 100;\n    \
+                 sub_14(7);\n    \
+                 halt;\n\
+             }\n\n\
+             fn sub_14(arg1) {\n    \
+                 output(arg1);\n    \
+                 return;\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn decompile_call_as_jump_target_correct_output() {
+        let memory: &[i64] = &[
+            21101, 0, 7, 0, // store return address 7
+            1105, 1, 11, // call 11, also the target of the jump below
+            1005, 100, 4,  // jnz [100], 4
+            99, // halt
+            109, 2, // arb 2
+            109, -2, // arb -2
+            2106, 0, 0, // return
+        ];
+
+        let output = Decompiler::new(memory).decompile();
+
+        assert_eq!(
+            output,
+            "var v100 = 0;\n\n\
+             fn main() {\n    \
+                 rb[0] = 7;\n    \
+                 while (true) {\n        \
+                     sub_11(_);\n        \
+                     if (v100 != 0) continue;\n        \
+                     break;\n    \
+                 }\n    \
+                 halt;\n\
+             }\n\n\
+             fn sub_11(arg1) {\n    \
+                 return;\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn decompile_boost_program_correct_output() {
+        let memory = loader::load(Path::new(BOOST_PATH)).unwrap();
+        let output = Decompiler::new(&memory).decompile();
+
+        assert!(output.contains(
+            "fn sub_922(arg1, arg2) {\n    \
+                 if (arg1 >= 3) {\n        \
+                     sub_922(arg1 + -1, _);\n"
+        ));
+        assert!(output.contains("                sub_922(27, _);\n"));
+    }
+}
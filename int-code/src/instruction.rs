@@ -0,0 +1,194 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::int_code::{Mode, OpCode};
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Parameter {
+    pub mode: Mode,
+    pub value: i64,
+}
+
+impl Parameter {
+    /// absolute address this parameter refers to, only known for position mode
+    pub fn static_address(self) -> Option<usize> {
+        match self.mode {
+            Mode::Position => usize::try_from(self.value).ok(),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Parameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.mode {
+            Mode::Position => write!(f, "[{}]", self.value),
+            Mode::Immediate => write!(f, "{}", self.value),
+            Mode::Relative => write!(f, "[rb{:+}]", self.value),
+        }
+    }
+}
+
+/// a single decoded instruction of a (static) int code image
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Instruction {
+    pub address: usize,
+    pub op_code: OpCode,
+    pub parameters: Vec<Parameter>,
+}
+
+impl Instruction {
+    pub fn decode(memory: &[i64], address: usize) -> Option<Self> {
+        let op_code = OpCode::decode(*memory.get(address)?)?;
+
+        let parameters = op_code
+            .parameter_modes()
+            .into_iter()
+            .enumerate()
+            .map(|(i, mode)| {
+                memory.get(address + 1 + i).map(|value| Parameter {
+                    mode,
+                    value: *value,
+                })
+            })
+            .collect::<Option<Vec<Parameter>>>()?;
+
+        Some(Instruction {
+            address,
+            op_code,
+            parameters,
+        })
+    }
+
+    pub fn next_address(&self) -> usize {
+        self.address + 1 + self.parameters.len()
+    }
+
+    pub fn contains(&self, address: usize) -> bool {
+        address >= self.address && address < self.next_address()
+    }
+
+    /// parameter the instruction writes its result to
+    pub fn write_parameter(&self) -> Option<Parameter> {
+        match self.op_code {
+            OpCode::Add { .. }
+            | OpCode::Mut { .. }
+            | OpCode::LessThan { .. }
+            | OpCode::Equals { .. } => self.parameters.get(2).copied(),
+            OpCode::Input { .. } => self.parameters.first().copied(),
+            _ => None,
+        }
+    }
+
+    pub fn is_jump(&self) -> bool {
+        matches!(
+            self.op_code,
+            OpCode::JumpIfTrue { .. } | OpCode::JumpIfFalse { .. }
+        )
+    }
+
+    /// jump target if it is encoded as an immediate value
+    pub fn jump_target(&self) -> Option<usize> {
+        if !self.is_jump() {
+            return None;
+        }
+
+        let target = self.parameters.get(1)?;
+
+        match target.mode {
+            Mode::Immediate => usize::try_from(target.value).ok(),
+            _ => None,
+        }
+    }
+
+    /// statically known outcome of a conditional jump with an immediate condition
+    pub fn constant_condition(&self) -> Option<bool> {
+        let condition = self.parameters.first()?;
+
+        if condition.mode != Mode::Immediate {
+            return None;
+        }
+
+        match self.op_code {
+            OpCode::JumpIfTrue { .. } => Some(condition.value != 0),
+            OpCode::JumpIfFalse { .. } => Some(condition.value == 0),
+            _ => None,
+        }
+    }
+
+    /// value the instruction stores, if it is an `add` / `mul` of two immediates
+    pub fn constant_store(&self) -> Option<i64> {
+        let (p1, p2) = (self.parameters.first()?, self.parameters.get(1)?);
+
+        if p1.mode != Mode::Immediate || p2.mode != Mode::Immediate {
+            return None;
+        }
+
+        match self.op_code {
+            OpCode::Add { .. } => p1.value.checked_add(p2.value),
+            OpCode::Mut { .. } => p1.value.checked_mul(p2.value),
+            _ => None,
+        }
+    }
+
+    /// immediate value added to the relative base
+    pub fn relative_base_adjustment(&self) -> Option<i64> {
+        match self.op_code {
+            OpCode::AdjustRelativeBase {
+                p1_mode: Mode::Immediate,
+            } => self.parameters.first().map(|p| p.value),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parameters: Vec<String> = self.parameters.iter().map(|p| p.to_string()).collect();
+
+        if parameters.is_empty() {
+            write!(f, "{}", self.op_code.mnemonic())
+        } else {
+            write!(f, "{} {}", self.op_code.mnemonic(), parameters.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decode_add_correct_output() {
+        let memory: &[i64] = &[1201, 4, 7, 5, 99];
+        let instruction = Instruction::decode(memory, 0).unwrap();
+
+        assert_eq!(instruction.next_address(), 4);
+        assert_eq!(instruction.to_string(), "add [rb+4], 7, [5]");
+        assert_eq!(
+            instruction.write_parameter(),
+            Some(Parameter {
+                mode: Mode::Position,
+                value: 5
+            })
+        );
+    }
+
+    #[test]
+    fn decode_invalid_mode_or_truncated_none() {
+        assert_eq!(Instruction::decode(&[301, 0, 0, 0], 0), None);
+        assert_eq!(Instruction::decode(&[1, 0, 0], 0), None);
+        assert_eq!(Instruction::decode(&[-1], 0), None);
+    }
+
+    #[test]
+    fn jump_target_and_constant_condition() {
+        let unconditional = Instruction::decode(&[1105, 1, 9], 0).unwrap();
+        assert_eq!(unconditional.jump_target(), Some(9));
+        assert_eq!(unconditional.constant_condition(), Some(true));
+
+        let computed = Instruction::decode(&[2106, 0, 0], 0).unwrap();
+        assert_eq!(computed.jump_target(), None);
+        assert_eq!(computed.constant_condition(), Some(true));
+    }
+}
@@ -0,0 +1,866 @@
+use std::convert::TryFrom;
+use std::io::Read;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use crate::coverage::Coverage;
+use crate::loader;
+use crate::mmio::{Device, MappedRegion};
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Mode {
+    Position,
+    Immediate,
+    Relative,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum OpCode {
+    Add {
+        p1_mode: Mode,
+        p2_mode: Mode,
+        p3_mode: Mode,
+    },
+    Mut {
+        p1_mode: Mode,
+        p2_mode: Mode,
+        p3_mode: Mode,
+    },
+    Input {
+        p1_mode: Mode,
+    },
+    Output {
+        p1_mode: Mode,
+    },
+    JumpIfTrue {
+        p1_mode: Mode,
+        p2_mode: Mode,
+    },
+    JumpIfFalse {
+        p1_mode: Mode,
+        p2_mode: Mode,
+    },
+    LessThan {
+        p1_mode: Mode,
+        p2_mode: Mode,
+        p3_mode: Mode,
+    },
+    Equals {
+        p1_mode: Mode,
+        p2_mode: Mode,
+        p3_mode: Mode,
+    },
+    AdjustRelativeBase {
+        p1_mode: Mode,
+    },
+    End,
+    Unknown,
+}
+
+impl OpCode {
+    fn get_mode(v: i64) -> Mode {
+        match v {
+            0 => Mode::Position,
+            1 => Mode::Immediate,
+            2 => Mode::Relative,
+            _ => panic!("unknown mode: {}", v),
+        }
+    }
+
+    fn is_valid_mode(v: i64) -> bool {
+        (0..=2).contains(&v)
+    }
+
+    pub fn from_i64(v: i64) -> OpCode {
+        let code = v % 100;
+
+        let p1_mode = OpCode::get_mode((v / 100) % 10);
+        let p2_mode = OpCode::get_mode((v / 1000) % 10);
+        let p3_mode = OpCode::get_mode((v / 10000) % 10);
+
+        match code {
+            1 => OpCode::Add {
+                p1_mode,
+                p2_mode,
+                p3_mode,
+            },
+            2 => OpCode::Mut {
+                p1_mode,
+                p2_mode,
+                p3_mode,
+            },
+            3 => OpCode::Input { p1_mode },
+            4 => OpCode::Output { p1_mode },
+            5 => OpCode::JumpIfTrue { p1_mode, p2_mode },
+            6 => OpCode::JumpIfFalse { p1_mode, p2_mode },
+            7 => OpCode::LessThan {
+                p1_mode,
+                p2_mode,
+                p3_mode,
+            },
+            8 => OpCode::Equals {
+                p1_mode,
+                p2_mode,
+                p3_mode,
+            },
+            9 => OpCode::AdjustRelativeBase { p1_mode },
+            99 => OpCode::End,
+            _ => OpCode::Unknown,
+        }
+    }
+
+    /// like `from_i64` but returns `None` instead of panicking on invalid modes
+    /// and for unknown op codes, so arbitrary (data) cells can be probed
+    pub fn decode(v: i64) -> Option<OpCode> {
+        if v < 0 {
+            return None;
+        }
+
+        let modes_valid = [(v / 100) % 10, (v / 1000) % 10, (v / 10000) % 10]
+            .iter()
+            .all(|m| OpCode::is_valid_mode(*m));
+
+        if !modes_valid {
+            return None;
+        }
+
+        match OpCode::from_i64(v) {
+            OpCode::Unknown => None,
+            op_code => Some(op_code),
+        }
+    }
+
+    pub fn parameter_modes(&self) -> Vec<Mode> {
+        match self {
+            OpCode::Add {
+                p1_mode,
+                p2_mode,
+                p3_mode,
+            }
+            | OpCode::Mut {
+                p1_mode,
+                p2_mode,
+                p3_mode,
+            }
+            | OpCode::LessThan {
+                p1_mode,
+                p2_mode,
+                p3_mode,
+            }
+            | OpCode::Equals {
+                p1_mode,
+                p2_mode,
+                p3_mode,
+            } => vec![*p1_mode, *p2_mode, *p3_mode],
+            OpCode::JumpIfTrue { p1_mode, p2_mode } | OpCode::JumpIfFalse { p1_mode, p2_mode } => {
+                vec![*p1_mode, *p2_mode]
+            }
+            OpCode::Input { p1_mode }
+            | OpCode::Output { p1_mode }
+            | OpCode::AdjustRelativeBase { p1_mode } => vec![*p1_mode],
+            OpCode::End | OpCode::Unknown => Vec::new(),
+        }
+    }
+
+    pub fn parameter_count(&self) -> usize {
+        self.parameter_modes().len()
+    }
+
+    pub fn mnemonic(&self) -> &'static str {
+        match self {
+            OpCode::Add { .. } => "add",
+            OpCode::Mut { .. } => "mul",
+            OpCode::Input { .. } => "in",
+            OpCode::Output { .. } => "out",
+            OpCode::JumpIfTrue { .. } => "jnz",
+            OpCode::JumpIfFalse { .. } => "jz",
+            OpCode::LessThan { .. } => "lt",
+            OpCode::Equals { .. } => "eq",
+            OpCode::AdjustRelativeBase { .. } => "arb",
+            OpCode::End => "halt",
+            OpCode::Unknown => "???",
+        }
+    }
+}
+
+/// a single memory cell change, `old` is `None` if the memory had to grow
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct MemoryWrite {
+    pub address: usize,
+    pub old: Option<i64>,
+    pub new: i64,
+}
+
+/// everything needed to undo one executed instruction
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct StepRecord {
+    pub steps: usize,
+    pub ip: usize,
+    pub rb: isize,
+    pub memory_len: usize,
+    pub write: Option<MemoryWrite>,
+    pub input: Option<i64>,
+    pub output: Option<i64>,
+    pub halted: bool,
+    /// the step wrote to a mapped device, which can't be undone
+    pub device_write: bool,
+    /// devices were mapped, they ticked and may have been read, which can't
+    /// be undone either
+    pub device_tick: bool,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct IntCode {
+    state: Vec<i64>,
+    steps: usize,
+    ip: usize,
+    rb: isize,
+    single_input_value: Option<i64>,
+    inputs: Vec<i64>,
+    done: bool,
+    outputs: Vec<i64>,
+    op_codes: Vec<OpCode>,
+    rb_history: Vec<isize>,
+    ignore_outputs: bool,
+    recording: bool,
+    history: Vec<StepRecord>,
+    collect_coverage: bool,
+    coverage: Coverage,
+    devices: Vec<MappedRegion>,
+}
+
+impl IntCode {
+    pub fn new(input_state: Vec<i64>, single_input_value: Option<i64>) -> Self {
+        IntCode {
+            state: input_state,
+            steps: 0,
+            ip: 0,
+            rb: 0,
+            single_input_value,
+            inputs: Vec::new(),
+            done: false,
+            outputs: Vec::new(),
+            op_codes: Vec::new(),
+            rb_history: Vec::new(),
+            ignore_outputs: false,
+            recording: false,
+            history: Vec::new(),
+            collect_coverage: false,
+            coverage: Coverage::new(),
+            devices: Vec::new(),
+        }
+    }
+
+    pub fn load(path: &Path, single_input_value: Option<i64>) -> Result<Self, String> {
+        let input = loader::load(path)?;
+
+        Ok(IntCode::new(input, single_input_value))
+    }
+
+    /// loads a text or binary image from any reader, e.g. stdin
+    pub fn from_reader<R: Read>(
+        reader: R,
+        single_input_value: Option<i64>,
+    ) -> Result<Self, String> {
+        let input = loader::read(reader)?;
+
+        Ok(IntCode::new(input, single_input_value))
+    }
+
+    pub fn run_for_two_outputs(&mut self, input: i64) -> Result<Option<(i64, i64)>, String> {
+        if self.done {
+            return Err("run_for_two_outputs called after int code is already done".to_owned());
+        }
+
+        self.inputs.push(input);
+
+        let mut outputs: Vec<i64> = Vec::new();
+
+        loop {
+            if outputs.len() == 2 {
+                return Ok(Some((outputs[0], outputs[1])));
+            }
+
+            let output = self.step()?;
+
+            if self.done {
+                break;
+            }
+
+            if let Some(o) = output {
+                outputs.push(o);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// runs until the program halts
+    pub fn run(&mut self) -> Result<(), String> {
+        while !self.done {
+            self.step()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn run_complete_program(&mut self) -> Result<i64, String> {
+        if self.done {
+            return Err("can only be run once".to_owned());
+        }
+
+        let mut last_output: Option<i64> = None;
+
+        loop {
+            let output = self.step()?;
+
+            if self.done {
+                break;
+            }
+
+            if let Some(lo) = last_output {
+                if !self.ignore_outputs && lo != 0 {
+                    return Err(format!("last output != 0: ip: {} lo: {}", self.ip, lo).to_owned());
+                }
+            }
+
+            last_output = output;
+        }
+
+        self.outputs
+            .last()
+            .ok_or_else(|| "no last output".to_owned())
+            .copied()
+    }
+
+    fn adjust_relative_base(&self, mode: Mode, offset: usize) -> Result<isize, String> {
+        let adjust_with = self.get_value_for_mode(mode, offset)? as isize;
+
+        Ok(self.rb + adjust_with)
+    }
+
+    pub fn step(&mut self) -> Result<Option<i64>, String> {
+        let op_code_val = self
+            .state
+            .get(self.ip)
+            .ok_or_else(|| "op_code_val error".to_owned())?;
+        let op_code = OpCode::from_i64(*op_code_val);
+
+        if self.collect_coverage {
+            self.coverage.record_instruction(self.ip);
+        }
+
+        self.devices.iter().for_each(|d| d.tick());
+
+        let mut record = if self.recording {
+            Some(self.begin_record(&op_code)?)
+        } else {
+            None
+        };
+
+        let mut output: Option<i64> = None;
+        let mut new_rb: Option<isize> = None;
+
+        match op_code {
+            OpCode::End => {
+                self.done = true;
+
+                if let Some(mut r) = record {
+                    r.halted = true;
+                    self.history.push(r);
+                }
+
+                return Ok(None);
+            }
+            OpCode::Output { p1_mode } => {
+                let o = self.get_value_for_mode(p1_mode, 1)?;
+                self.outputs.push(o);
+                output = Some(o);
+            }
+            OpCode::AdjustRelativeBase { p1_mode } => {
+                new_rb = Some(self.adjust_relative_base(p1_mode, 1)?);
+            }
+            OpCode::Unknown => return Err("Unknown opcode".to_owned()),
+            _ => (),
+        }
+
+        let next_state: Vec<i64> = match &op_code {
+            OpCode::Add {
+                p1_mode,
+                p2_mode,
+                p3_mode,
+            } => self.calc_next_state(*p1_mode, *p2_mode, *p3_mode, &|v1, v2| v1 + v2)?,
+            OpCode::Mut {
+                p1_mode,
+                p2_mode,
+                p3_mode,
+            } => self.calc_next_state(*p1_mode, *p2_mode, *p3_mode, &|v1, v2| v1 * v2)?,
+            OpCode::LessThan {
+                p1_mode,
+                p2_mode,
+                p3_mode,
+            } => self.calc_next_state(*p1_mode, *p2_mode, *p3_mode, &|v1, v2| {
+                if v1 < v2 {
+                    1
+                } else {
+                    0
+                }
+            })?,
+            OpCode::Equals {
+                p1_mode,
+                p2_mode,
+                p3_mode,
+            } => self.calc_next_state(*p1_mode, *p2_mode, *p3_mode, &|v1, v2| {
+                if v1 == v2 {
+                    1
+                } else {
+                    0
+                }
+            })?,
+            OpCode::Input { p1_mode } => self.use_input_for_next_state(*p1_mode, 1)?,
+            _ => self.state.to_owned(),
+        };
+
+        let (next_ip, taken) = self.calc_next_ip(&op_code)?;
+
+        if let Some(taken) = taken.filter(|_| self.collect_coverage) {
+            self.coverage.record_branch(self.ip, taken);
+        }
+
+        if let Some(r) = record.as_mut() {
+            if let Some(w) = r.write.as_mut() {
+                w.new = next_state[w.address];
+            }
+
+            r.output = output;
+        }
+
+        if let Some(nrb) = new_rb {
+            self.rb_history.push(self.rb);
+            self.rb = nrb;
+        }
+
+        self.state = next_state;
+        self.ip = next_ip;
+        self.steps += 1;
+        self.op_codes.push(op_code);
+
+        if let Some(r) = record {
+            self.history.push(r);
+        }
+
+        Ok(output)
+    }
+
+    fn begin_record(&self, op_code: &OpCode) -> Result<StepRecord, String> {
+        let write_target = match op_code {
+            OpCode::Add { p3_mode, .. }
+            | OpCode::Mut { p3_mode, .. }
+            | OpCode::LessThan { p3_mode, .. }
+            | OpCode::Equals { p3_mode, .. } => Some(self.get_target_pos(*p3_mode, self.ip + 3)?),
+            OpCode::Input { p1_mode } => Some(self.get_target_pos(*p1_mode, self.ip + 1)?),
+            _ => None,
+        };
+        let device_write = write_target.is_some_and(|a| self.mapped_region(a).is_some());
+
+        let input = match op_code {
+            OpCode::Input { .. } if self.single_input_value.is_none() => {
+                self.inputs.last().copied()
+            }
+            _ => None,
+        };
+
+        Ok(StepRecord {
+            steps: self.steps,
+            ip: self.ip,
+            rb: self.rb,
+            memory_len: self.state.len(),
+            write: write_target
+                .filter(|_| !device_write)
+                .map(|address| MemoryWrite {
+                    address,
+                    old: self.state.get(address).copied(),
+                    new: 0,
+                }),
+            input,
+            output: None,
+            halted: false,
+            device_write,
+            device_tick: !self.devices.is_empty(),
+        })
+    }
+
+    /// undo the last recorded step, steps run while devices were mapped
+    /// can't be undone
+    pub fn step_back(&mut self) -> Result<(), String> {
+        match self.history.last() {
+            None => return Err("no recorded step to go back to".to_owned()),
+            Some(r) if r.device_write => {
+                return Err(format!("step {} wrote to a device", r.steps));
+            }
+            Some(r) if r.device_tick => {
+                return Err(format!("step {} ticked a device", r.steps));
+            }
+            _ => (),
+        }
+
+        let record = self.history.pop().unwrap();
+
+        self.done = false;
+
+        if record.halted {
+            return Ok(());
+        }
+
+        if let Some(w) = record.write {
+            if let Some(old) = w.old {
+                self.state[w.address] = old;
+            }
+        }
+        self.state.truncate(record.memory_len);
+
+        if let Some(OpCode::AdjustRelativeBase { .. }) = self.op_codes.pop() {
+            self.rb_history.pop();
+        }
+
+        if let Some(i) = record.input {
+            self.inputs.push(i);
+        }
+
+        if record.output.is_some() {
+            self.outputs.pop();
+        }
+
+        self.ip = record.ip;
+        self.rb = record.rb;
+        self.steps = record.steps;
+
+        Ok(())
+    }
+
+    /// go back to the state right before the recorded step `steps` was executed
+    pub fn rewind_to(&mut self, steps: usize) -> Result<(), String> {
+        let position = self
+            .history
+            .iter()
+            .position(|r| r.steps == steps && !r.halted)
+            .ok_or_else(|| format!("step {} was not recorded", steps))?;
+
+        while self.history.len() > position {
+            self.step_back()?;
+        }
+
+        Ok(())
+    }
+
+    /// the most recent recorded step which wrote to `address`
+    pub fn last_write_to(&self, address: usize) -> Option<&StepRecord> {
+        self.history
+            .iter()
+            .rev()
+            .find(|r| r.write.is_some_and(|w| w.address == address))
+    }
+
+    /// next ip and whether a conditional jump was taken
+    fn calc_next_ip(&self, op_code: &OpCode) -> Result<(usize, Option<bool>), String> {
+        let next_ip = match op_code {
+            OpCode::Add { .. }
+            | OpCode::Mut { .. }
+            | OpCode::LessThan { .. }
+            | OpCode::Equals { .. } => self.ip + 4,
+            OpCode::Input { .. } | OpCode::Output { .. } | OpCode::AdjustRelativeBase { .. } => {
+                self.ip + 2
+            }
+            OpCode::JumpIfFalse { p1_mode, p2_mode } => {
+                return self.conditional_jump(*p1_mode, *p2_mode, false);
+            }
+            OpCode::JumpIfTrue { p1_mode, p2_mode } => {
+                return self.conditional_jump(*p1_mode, *p2_mode, true);
+            }
+            _ => self.ip,
+        };
+
+        Ok((next_ip, None))
+    }
+
+    fn conditional_jump(
+        &self,
+        p1_mode: Mode,
+        p2_mode: Mode,
+        jump_if_true: bool,
+    ) -> Result<(usize, Option<bool>), String> {
+        let val1: i64 = self.get_value_for_mode(p1_mode, 1)?;
+        let val2: i64 = self.get_value_for_mode(p2_mode, 2)?;
+
+        let do_jump = if jump_if_true { val1 != 0 } else { val1 == 0 };
+
+        if do_jump {
+            let valid_address =
+                usize::try_from(val2).map_err(|_e| format!("invalid jump address: {}", val2))?;
+            return Ok((valid_address, Some(true)));
+        }
+
+        Ok((self.ip + 3, Some(false)))
+    }
+
+    fn use_input_for_next_state(&mut self, mode: Mode, offset: usize) -> Result<Vec<i64>, String> {
+        let input = if let Some(single_input_value) = self.single_input_value {
+            single_input_value
+        } else {
+            self.inputs
+                .pop()
+                .ok_or_else(|| "no input value available".to_owned())?
+        };
+
+        let next_state = self.set_value_for_mode(mode, offset, input)?;
+
+        Ok(next_state)
+    }
+
+    fn calc_next_state(
+        &self,
+        p1_mode: Mode,
+        p2_mode: Mode,
+        p3_mode: Mode,
+        f: &dyn Fn(i64, i64) -> i64,
+    ) -> Result<Vec<i64>, String> {
+        if p3_mode == Mode::Immediate {
+            return Err("p3_mode is immediate".to_owned());
+        }
+
+        let val1: i64 = self.get_value_for_mode(p1_mode, 1)?;
+        let val2: i64 = self.get_value_for_mode(p2_mode, 2)?;
+
+        let new_value = f(val1, val2);
+
+        let next_state = self.set_value_for_mode(p3_mode, 3, new_value)?;
+
+        Ok(next_state)
+    }
+
+    fn set_value_for_mode(
+        &self,
+        mode: Mode,
+        offset: usize,
+        new_value: i64,
+    ) -> Result<Vec<i64>, String> {
+        let mut next_state = self.state.to_owned();
+        let pos = self.ip + offset;
+
+        let target_pos: usize = self.get_target_pos(mode, pos)?;
+
+        if let Some(region) = self.mapped_region(target_pos) {
+            region.write(target_pos, new_value);
+            return Ok(next_state);
+        }
+
+        if target_pos >= next_state.len() {
+            next_state.resize(target_pos + 1, 0);
+        }
+
+        next_state
+            .get_mut(target_pos)
+            .map(|v| *v = new_value)
+            .ok_or_else(|| "target_pos write error".to_owned())?;
+
+        Ok(next_state)
+    }
+
+    // get value for a specific pos according to the mode
+    fn get_value_for_mode(&self, mode: Mode, offset: usize) -> Result<i64, String> {
+        let pos = self.ip + offset;
+
+        let pos_translated: usize = self.get_target_pos(mode, pos)?;
+
+        if mode != Mode::Immediate {
+            if let Some(region) = self.mapped_region(pos_translated) {
+                return Ok(region.read(pos_translated));
+            }
+        }
+
+        let v = *self.state.get(pos_translated).unwrap_or(&0);
+
+        Ok(v)
+    }
+
+    fn get_target_pos(&self, mode: Mode, pos: usize) -> Result<usize, String> {
+        let target_pos: usize = match mode {
+            Mode::Position => usize::try_from(
+                *self
+                    .state
+                    .get(pos)
+                    .ok_or_else(|| "target_pos error".to_owned())?,
+            )
+            .map_err(|e| e.to_string())?,
+            Mode::Relative => {
+                let offset = *self
+                    .state
+                    .get(pos)
+                    .ok_or_else(|| "Relative pos_translated error".to_owned())?;
+
+                let relative_pos = self.rb + offset as isize;
+
+                usize::try_from(relative_pos).map_err(|e| e.to_string())?
+            }
+
+            _ => pos,
+        };
+
+        Ok(target_pos)
+    }
+
+    pub fn set_memory(&mut self, address: usize, value: i64) {
+        if address >= self.state.len() {
+            self.state.resize(address + 1, 0);
+        }
+
+        self.state[address] = value;
+    }
+
+    /// queue inputs, which are consumed in the given order
+    pub fn set_inputs(&mut self, inputs: &[i64]) {
+        self.inputs = inputs.iter().rev().copied().collect();
+    }
+
+    /// queue one more input after the already queued ones
+    pub fn push_input(&mut self, input: i64) {
+        self.inputs.insert(0, input);
+    }
+
+    /// the next instruction reads input but none is available
+    pub fn needs_input(&self) -> bool {
+        if self.done || self.single_input_value.is_some() || !self.inputs.is_empty() {
+            return false;
+        }
+
+        let op_code = self.state.get(self.ip).copied().and_then(OpCode::decode);
+
+        matches!(op_code, Some(OpCode::Input { .. }))
+    }
+
+    pub fn set_ignore_outputs(&mut self, v: bool) {
+        self.ignore_outputs = v;
+    }
+
+    /// record memory writes and register changes of every step, which allows
+    /// `step_back` / `rewind_to` without re-running the program
+    pub fn set_recording(&mut self, v: bool) {
+        self.recording = v;
+    }
+
+    /// route reads and writes of `start..start + len` to `device` instead of
+    /// memory, instruction fetches are not affected
+    pub fn map_device<D: Device + 'static>(
+        &mut self,
+        start: usize,
+        len: usize,
+        device: Arc<Mutex<D>>,
+    ) -> Result<(), String> {
+        let region = MappedRegion::new(start, len, device);
+
+        if let Some(other) = self.devices.iter().find(|d| d.overlaps(&region)) {
+            return Err(format!("{:?} overlaps {:?}", region, other));
+        }
+
+        self.devices.push(region);
+
+        Ok(())
+    }
+
+    fn mapped_region(&self, address: usize) -> Option<&MappedRegion> {
+        self.devices.iter().find(|d| d.contains(address))
+    }
+
+    /// count executed instructions and branch outcomes, see `Coverage::report`
+    pub fn set_coverage(&mut self, v: bool) {
+        self.collect_coverage = v;
+    }
+
+    pub fn get_coverage(&self) -> &Coverage {
+        &self.coverage
+    }
+
+    pub fn get_history(&self) -> &Vec<StepRecord> {
+        self.history.as_ref()
+    }
+
+    pub fn get_memory(&self) -> &Vec<i64> {
+        self.state.as_ref()
+    }
+
+    pub fn get_ip(&self) -> usize {
+        self.ip
+    }
+
+    pub fn get_relative_base(&self) -> isize {
+        self.rb
+    }
+
+    pub fn get_all_outputs(&self) -> &Vec<i64> {
+        self.outputs.as_ref()
+    }
+
+    pub fn get_steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // in [rb+0]; add [rb+0], 5, [9]; out [9]; halt
+    fn recorded_program() -> IntCode {
+        let mut int_code = IntCode::new(vec![203, 0, 1201, 0, 5, 9, 4, 9, 99], None);
+        int_code.set_recording(true);
+        int_code.inputs.push(37);
+        int_code
+    }
+
+    #[test]
+    fn step_back_restores_state() {
+        let mut int_code = recorded_program();
+        let initial = int_code.clone();
+
+        while !int_code.is_done() {
+            int_code.step().unwrap();
+        }
+
+        assert_eq!(int_code.get_all_outputs(), &vec![42]);
+        assert_eq!(int_code.get_memory().len(), 10);
+        assert_eq!(int_code.get_history().len(), 4);
+
+        while int_code.step_back().is_ok() {}
+
+        assert_eq!(int_code.state, initial.state);
+        assert_eq!(int_code.inputs, initial.inputs);
+        assert_eq!(int_code.outputs, initial.outputs);
+        assert_eq!(int_code.ip, initial.ip);
+        assert_eq!(int_code.steps, initial.steps);
+        assert!(!int_code.is_done());
+    }
+
+    #[test]
+    fn rewind_to_and_last_write_correct_output() {
+        let mut int_code = recorded_program();
+
+        while !int_code.is_done() {
+            int_code.step().unwrap();
+        }
+
+        let last_write = int_code.last_write_to(9).unwrap();
+        assert_eq!((last_write.steps, last_write.ip), (1, 2));
+        assert_eq!(int_code.last_write_to(3), None);
+
+        int_code.rewind_to(2).unwrap();
+        assert_eq!(int_code.get_ip(), 6);
+        assert!(int_code.get_all_outputs().is_empty());
+
+        assert_eq!(int_code.step().unwrap(), Some(42));
+        assert!(int_code.rewind_to(7).is_err());
+    }
+}
@@ -0,0 +1,13 @@
+mod int_code;
+
+pub mod cfg;
+pub mod coverage;
+pub mod decompiler;
+pub mod instruction;
+pub mod loader;
+pub mod mmio;
+pub mod scheduler;
+pub mod sweep;
+pub mod symbolic;
+
+pub use int_code::{IntCode, MemoryWrite, Mode, OpCode, StepRecord};
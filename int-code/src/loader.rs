@@ -0,0 +1,212 @@
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// magic prefix of the compact binary format, followed by zigzag LEB128 varints
+pub static BINARY_MAGIC: &[u8] = b"ICv1";
+
+/// parses an int code image from text: values separated by commas and / or
+/// whitespace, `#` starts a comment until the end of the line
+pub fn parse_text(text: &str) -> Result<Vec<i64>, String> {
+    let mut memory: Vec<i64> = Vec::new();
+
+    for (line_index, line) in text.lines().enumerate() {
+        let code = match line.find('#') {
+            Some(i) => &line[..i],
+            None => line,
+        };
+
+        for token in code.split(|c: char| c == ',' || c.is_whitespace()) {
+            if !token.is_empty() {
+                let value = token.parse::<i64>().map_err(|e| {
+                    // separators may be multibyte whitespace, the byte offset
+                    // of the token within the line is exact
+                    let offset = token.as_ptr() as usize - code.as_ptr() as usize;
+
+                    format!(
+                        "line {}, column {}, token {}: {} ('{}')",
+                        line_index + 1,
+                        code[..offset].chars().count() + 1,
+                        memory.len(),
+                        e,
+                        token
+                    )
+                })?;
+
+                memory.push(value);
+            }
+        }
+    }
+
+    Ok(memory)
+}
+
+pub fn encode_binary(memory: &[i64]) -> Vec<u8> {
+    let mut bytes = BINARY_MAGIC.to_vec();
+
+    for value in memory {
+        let mut zigzag = ((value << 1) ^ (value >> 63)) as u64;
+
+        loop {
+            let byte = (zigzag & 0x7f) as u8;
+            zigzag >>= 7;
+
+            if zigzag == 0 {
+                bytes.push(byte);
+                break;
+            }
+
+            bytes.push(byte | 0x80);
+        }
+    }
+
+    bytes
+}
+
+pub fn decode_binary(bytes: &[u8]) -> Result<Vec<i64>, String> {
+    if !bytes.starts_with(BINARY_MAGIC) {
+        return Err("missing binary int code header".to_owned());
+    }
+
+    let mut memory: Vec<i64> = Vec::new();
+    let mut zigzag: u64 = 0;
+    let mut shift = 0;
+
+    for (offset, byte) in bytes.iter().enumerate().skip(BINARY_MAGIC.len()) {
+        if shift >= 64 {
+            return Err(format!(
+                "byte {}, token {}: varint too long",
+                offset,
+                memory.len()
+            ));
+        }
+
+        // the 10th byte only holds the highest bit
+        if shift == 63 && byte & 0x7e != 0 {
+            return Err(format!(
+                "byte {}, token {}: varint overflows 64 bits",
+                offset,
+                memory.len()
+            ));
+        }
+
+        zigzag |= u64::from(byte & 0x7f) << shift;
+        shift += 7;
+
+        if byte & 0x80 == 0 {
+            memory.push(((zigzag >> 1) as i64) ^ -((zigzag & 1) as i64));
+            zigzag = 0;
+            shift = 0;
+        }
+    }
+
+    if shift != 0 {
+        return Err(format!(
+            "byte {}, token {}: truncated varint",
+            bytes.len(),
+            memory.len()
+        ));
+    }
+
+    Ok(memory)
+}
+
+/// reads an image in text or binary format (detected by the header) from
+/// any reader, e.g. `io::stdin()` or a byte slice
+pub fn read<R: Read>(mut reader: R) -> Result<Vec<i64>, String> {
+    let mut bytes: Vec<u8> = Vec::new();
+    reader.read_to_end(&mut bytes).map_err(|e| e.to_string())?;
+
+    if bytes.starts_with(BINARY_MAGIC) {
+        return decode_binary(&bytes);
+    }
+
+    let text = String::from_utf8(bytes).map_err(|e| e.to_string())?;
+
+    parse_text(&text)
+}
+
+/// reads an image from a file, `-` reads from stdin
+pub fn load(path: &Path) -> Result<Vec<i64>, String> {
+    if path == Path::new("-") {
+        return read(io::stdin().lock());
+    }
+
+    let file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    read(file).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_text_separators_and_comments_correct_output() {
+        let text = "# header\n1,0, 0,3\n\n2 4 4 5 # mul\n\t99,\n";
+
+        assert_eq!(parse_text(text), Ok(vec![1, 0, 0, 3, 2, 4, 4, 5, 99]));
+    }
+
+    #[test]
+    fn parse_text_invalid_token_position_error() {
+        let text = "1,0,0,3\n2, 4,x4,5\n";
+
+        assert_eq!(
+            parse_text(text),
+            Err("line 2, column 6, token 6: invalid digit found in string ('x4')".to_owned())
+        );
+    }
+
+    #[test]
+    fn parse_text_multibyte_separator_position_error() {
+        assert_eq!(
+            parse_text("1\u{3000}x"),
+            Err("line 1, column 3, token 1: invalid digit found in string ('x')".to_owned())
+        );
+    }
+
+    #[test]
+    fn binary_round_trip_correct_output() {
+        let memory: Vec<i64> = vec![0, 1, -1, 99, 1_125_899_906_842_624, i64::MIN, i64::MAX];
+        let bytes = encode_binary(&memory);
+
+        assert_eq!(
+            bytes.len(),
+            BINARY_MAGIC.len() + 1 + 1 + 1 + 2 + 8 + 10 + 10
+        );
+        assert_eq!(decode_binary(&bytes), Ok(memory.clone()));
+        assert_eq!(read(&bytes[..]), Ok(memory));
+    }
+
+    #[test]
+    fn decode_binary_truncated_error() {
+        let mut bytes = encode_binary(&[300]);
+        bytes.pop();
+
+        assert_eq!(
+            decode_binary(&bytes),
+            Err("byte 5, token 0: truncated varint".to_owned())
+        );
+    }
+
+    #[test]
+    fn read_text_from_reader_correct_output() {
+        assert_eq!(
+            read("104,1125899906842624,99".as_bytes()),
+            Ok(vec![104, 1_125_899_906_842_624, 99])
+        );
+    }
+
+    #[test]
+    fn decode_binary_overflow_error() {
+        let mut bytes = BINARY_MAGIC.to_vec();
+        bytes.extend_from_slice(&[0xff; 9]);
+        bytes.push(0x03);
+
+        assert_eq!(
+            decode_binary(&bytes),
+            Err("byte 13, token 0: varint overflows 64 bits".to_owned())
+        );
+    }
+}
@@ -0,0 +1,262 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// host side of a memory-mapped region, `offset` is relative to the start of
+/// the region
+pub trait Device: Send {
+    fn read(&mut self, offset: usize) -> i64;
+
+    fn write(&mut self, offset: usize, value: i64);
+
+    /// called once per executed instruction
+    fn tick(&mut self) {}
+}
+
+/// a device mapped into the address space of an `IntCode` machine, clones
+/// share the same device
+#[derive(Clone)]
+pub struct MappedRegion {
+    pub start: usize,
+    pub len: usize,
+    device: Arc<Mutex<dyn Device>>,
+}
+
+impl MappedRegion {
+    pub fn new(start: usize, len: usize, device: Arc<Mutex<dyn Device>>) -> Self {
+        MappedRegion { start, len, device }
+    }
+
+    pub fn contains(&self, address: usize) -> bool {
+        address >= self.start && address < self.start + self.len
+    }
+
+    pub fn overlaps(&self, other: &MappedRegion) -> bool {
+        self.start < other.start + other.len && other.start < self.start + self.len
+    }
+
+    pub fn read(&self, address: usize) -> i64 {
+        self.device.lock().unwrap().read(address - self.start)
+    }
+
+    pub fn write(&self, address: usize, value: i64) {
+        self.device
+            .lock()
+            .unwrap()
+            .write(address - self.start, value)
+    }
+
+    pub fn tick(&self) {
+        self.device.lock().unwrap().tick()
+    }
+}
+
+impl fmt::Debug for MappedRegion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MappedRegion({}..{})", self.start, self.start + self.len)
+    }
+}
+
+impl PartialEq for MappedRegion {
+    fn eq(&self, other: &Self) -> bool {
+        self.start == other.start
+            && self.len == other.len
+            && Arc::ptr_eq(&self.device, &other.device)
+    }
+}
+
+impl Eq for MappedRegion {}
+
+/// `width * height` cells, rendered as a grid of `#` (non zero) and ` `
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Framebuffer {
+    width: usize,
+    height: usize,
+    pixels: Vec<i64>,
+}
+
+impl Framebuffer {
+    pub fn new(width: usize, height: usize) -> Self {
+        Framebuffer {
+            width,
+            height,
+            pixels: vec![0; width * height],
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<i64> {
+        if x >= self.width {
+            return None;
+        }
+
+        self.pixels.get(y * self.width + x).copied()
+    }
+
+    pub fn render(&self) -> String {
+        self.pixels
+            .chunks(self.width.max(1))
+            .take(self.height)
+            .map(|row| {
+                row.iter()
+                    .map(|p| if *p == 0 { ' ' } else { '#' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl Device for Framebuffer {
+    fn read(&mut self, offset: usize) -> i64 {
+        self.pixels.get(offset).copied().unwrap_or(0)
+    }
+
+    fn write(&mut self, offset: usize, value: i64) {
+        if let Some(p) = self.pixels.get_mut(offset) {
+            *p = value;
+        }
+    }
+}
+
+/// single cell counting executed instructions, writing sets the counter
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct Clock {
+    ticks: i64,
+}
+
+impl Clock {
+    pub fn new() -> Self {
+        Clock::default()
+    }
+
+    pub fn ticks(&self) -> i64 {
+        self.ticks
+    }
+}
+
+impl Device for Clock {
+    fn read(&mut self, _offset: usize) -> i64 {
+        self.ticks
+    }
+
+    fn write(&mut self, _offset: usize, value: i64) {
+        self.ticks = value;
+    }
+
+    fn tick(&mut self) {
+        self.ticks += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::int_code::IntCode;
+
+    // counts reads, always reads 1
+    #[derive(Default)]
+    struct ReadCounter {
+        reads: usize,
+    }
+
+    impl Device for ReadCounter {
+        fn read(&mut self, _offset: usize) -> i64 {
+            self.reads += 1;
+            1
+        }
+
+        fn write(&mut self, _offset: usize, _value: i64) {}
+    }
+
+    #[test]
+    fn framebuffer_written_by_program_rendered() {
+        // draw a diagonal on a 3x3 framebuffer at 100..109
+        let mut int_code = IntCode::new(
+            vec![1101, 0, 1, 100, 1101, 0, 1, 104, 1101, 0, 1, 108, 99],
+            None,
+        );
+
+        let framebuffer = Arc::new(Mutex::new(Framebuffer::new(3, 3)));
+        int_code.map_device(100, 9, framebuffer.clone()).unwrap();
+        int_code.run().unwrap();
+
+        assert_eq!(framebuffer.lock().unwrap().render(), "#  \n # \n  #");
+        assert_eq!(int_code.get_memory().len(), 13);
+    }
+
+    #[test]
+    fn clock_read_by_program_correct_output() {
+        // nop; nop; out [50]; halt
+        let mut int_code = IntCode::new(vec![1101, 0, 0, 20, 1101, 0, 0, 20, 4, 50, 99], None);
+
+        let clock = Arc::new(Mutex::new(Clock::new()));
+        int_code.map_device(50, 1, clock.clone()).unwrap();
+        int_code.run().unwrap();
+
+        assert_eq!(int_code.get_all_outputs(), &vec![3]);
+        assert_eq!(clock.lock().unwrap().ticks(), 4);
+    }
+
+    #[test]
+    fn map_device_overlapping_error() {
+        let mut int_code = IntCode::new(vec![99], None);
+
+        int_code
+            .map_device(10, 5, Arc::new(Mutex::new(Clock::new())))
+            .unwrap();
+
+        assert!(int_code
+            .map_device(14, 1, Arc::new(Mutex::new(Clock::new())))
+            .is_err());
+    }
+
+    #[test]
+    fn coverage_branch_on_device_read_once() {
+        // jnz [50], 4; halt
+        let mut int_code = IntCode::new(vec![1005, 50, 4, 99, 99], None);
+
+        let counter = Arc::new(Mutex::new(ReadCounter::default()));
+        int_code.map_device(50, 1, counter.clone()).unwrap();
+        int_code.set_coverage(true);
+        int_code.run().unwrap();
+
+        assert_eq!(counter.lock().unwrap().reads, 1);
+        assert_eq!(int_code.get_ip(), 4);
+    }
+
+    #[test]
+    fn step_back_device_write_error() {
+        // add 1, 1, [50]; halt
+        let mut int_code = IntCode::new(vec![1101, 1, 1, 50, 99], None);
+
+        int_code
+            .map_device(50, 1, Arc::new(Mutex::new(ReadCounter::default())))
+            .unwrap();
+        int_code.set_recording(true);
+        int_code.step().unwrap();
+
+        assert_eq!(
+            int_code.step_back(),
+            Err("step 0 wrote to a device".to_owned())
+        );
+        assert_eq!(int_code.get_ip(), 4);
+    }
+
+    #[test]
+    fn step_back_device_tick_error() {
+        // add 0, 0, [7]; out [8]; halt
+        let mut int_code = IntCode::new(vec![1101, 0, 0, 7, 4, 8, 99, 0, 0], None);
+
+        let clock = Arc::new(Mutex::new(Clock::new()));
+        int_code.set_recording(true);
+        int_code.step().unwrap();
+        int_code.map_device(8, 1, clock.clone()).unwrap();
+        assert_eq!(int_code.step(), Ok(Some(1)));
+
+        assert_eq!(
+            int_code.step_back(),
+            Err("step 1 ticked a device".to_owned())
+        );
+        assert_eq!(clock.lock().unwrap().ticks(), 1);
+        assert_eq!(int_code.get_history().len(), 2);
+    }
+}
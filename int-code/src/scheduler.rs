@@ -0,0 +1,318 @@
+use std::path::Path;
+
+use crate::int_code::IntCode;
+use crate::loader;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Policy {
+    RoundRobin,
+    Priority,
+}
+
+/// named connection from the outputs of one machine to the inputs of another
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Channel {
+    pub name: String,
+    pub from: String,
+    pub to: String,
+    pub messages: Vec<i64>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Outcome {
+    Halted,
+    /// every machine which has not halted is waiting for input
+    Deadlock(Vec<String>),
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+struct Task {
+    name: String,
+    int_code: IntCode,
+    priority: i64,
+}
+
+/// runs many int code machines cooperatively, each gets up to `time_slice`
+/// steps before the next one is scheduled
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Scheduler {
+    tasks: Vec<Task>,
+    channels: Vec<Channel>,
+    policy: Policy,
+    time_slice: usize,
+}
+
+impl Scheduler {
+    pub fn new(policy: Policy, time_slice: usize) -> Self {
+        Scheduler {
+            tasks: Vec::new(),
+            channels: Vec::new(),
+            policy,
+            time_slice: time_slice.max(1),
+        }
+    }
+
+    /// builds a scheduler from a topology description, program paths are
+    /// relative to `base`:
+    ///
+    /// ```text
+    /// policy round-robin        # or priority
+    /// slice 100
+    /// machine a amp.txt 1       # name, program, optional priority
+    /// channel a_to_b a -> b
+    /// input a 9,0
+    /// ```
+    pub fn from_topology(text: &str, base: &Path) -> Result<Self, String> {
+        let mut scheduler = Scheduler::new(Policy::RoundRobin, 1);
+
+        for (i, line) in text.lines().enumerate() {
+            let code = line.split('#').next().unwrap_or("");
+            let words: Vec<&str> = code.split_whitespace().collect();
+
+            scheduler
+                .apply_topology_line(&words, base)
+                .map_err(|e| format!("line {}: {}", i + 1, e))?;
+        }
+
+        Ok(scheduler)
+    }
+
+    fn apply_topology_line(&mut self, words: &[&str], base: &Path) -> Result<(), String> {
+        match words {
+            [] => Ok(()),
+            ["policy", "round-robin"] => {
+                self.policy = Policy::RoundRobin;
+                Ok(())
+            }
+            ["policy", "priority"] => {
+                self.policy = Policy::Priority;
+                Ok(())
+            }
+            ["slice", steps] => {
+                let steps = steps.parse::<usize>().map_err(|e| e.to_string())?;
+                self.time_slice = steps.max(1);
+                Ok(())
+            }
+            ["machine", name, path] | ["machine", name, path, _] => {
+                let priority = match words.get(3) {
+                    Some(p) => p.parse::<i64>().map_err(|e| e.to_string())?,
+                    None => 0,
+                };
+                let program = loader::load(&base.join(path))?;
+
+                self.add_machine(name, IntCode::new(program, None), priority)
+            }
+            ["channel", name, from, "->", to] => self.add_channel(name, from, to),
+            ["input", name, values] => {
+                let values = loader::parse_text(values)?;
+                let task = self.task_mut(name)?;
+                values.iter().for_each(|v| task.int_code.push_input(*v));
+                Ok(())
+            }
+            _ => Err(format!("invalid topology line: '{}'", words.join(" "))),
+        }
+    }
+
+    pub fn add_machine(
+        &mut self,
+        name: &str,
+        int_code: IntCode,
+        priority: i64,
+    ) -> Result<(), String> {
+        if self.tasks.iter().any(|t| t.name == name) {
+            return Err(format!("duplicate machine: {}", name));
+        }
+
+        self.tasks.push(Task {
+            name: name.to_owned(),
+            int_code,
+            priority,
+        });
+
+        Ok(())
+    }
+
+    pub fn add_channel(&mut self, name: &str, from: &str, to: &str) -> Result<(), String> {
+        if self.channels.iter().any(|c| c.name == name) {
+            return Err(format!("duplicate channel: {}", name));
+        }
+
+        self.task(from)?;
+        self.task(to)?;
+
+        self.channels.push(Channel {
+            name: name.to_owned(),
+            from: from.to_owned(),
+            to: to.to_owned(),
+            messages: Vec::new(),
+        });
+
+        Ok(())
+    }
+
+    fn task(&self, name: &str) -> Result<&Task, String> {
+        self.tasks
+            .iter()
+            .find(|t| t.name == name)
+            .ok_or_else(|| format!("unknown machine: {}", name))
+    }
+
+    fn task_mut(&mut self, name: &str) -> Result<&mut Task, String> {
+        self.tasks
+            .iter_mut()
+            .find(|t| t.name == name)
+            .ok_or_else(|| format!("unknown machine: {}", name))
+    }
+
+    pub fn machine(&self, name: &str) -> Option<&IntCode> {
+        self.task(name).ok().map(|t| &t.int_code)
+    }
+
+    pub fn channel(&self, name: &str) -> Option<&Channel> {
+        self.channels.iter().find(|c| c.name == name)
+    }
+
+    fn is_runnable(task: &Task) -> bool {
+        !task.int_code.is_done() && !task.int_code.needs_input()
+    }
+
+    /// runs until every machine halted or all remaining ones wait for input
+    pub fn run(&mut self) -> Result<Outcome, String> {
+        loop {
+            let runnable: Vec<usize> = (0..self.tasks.len())
+                .filter(|i| Scheduler::is_runnable(&self.tasks[*i]))
+                .collect();
+
+            if runnable.is_empty() {
+                let blocked: Vec<String> = self
+                    .tasks
+                    .iter()
+                    .filter(|t| !t.int_code.is_done())
+                    .map(|t| t.name.clone())
+                    .collect();
+
+                if blocked.is_empty() {
+                    return Ok(Outcome::Halted);
+                }
+
+                return Ok(Outcome::Deadlock(blocked));
+            }
+
+            match self.policy {
+                Policy::RoundRobin => {
+                    for i in runnable {
+                        self.run_slice(i)?;
+                    }
+                }
+                Policy::Priority => {
+                    // highest priority first, the earliest added one on ties
+                    let i = runnable
+                        .iter()
+                        .copied()
+                        .min_by_key(|i| (-self.tasks[*i].priority, *i))
+                        .unwrap();
+
+                    self.run_slice(i)?;
+                }
+            }
+        }
+    }
+
+    fn run_slice(&mut self, index: usize) -> Result<(), String> {
+        for _ in 0..self.time_slice {
+            if !Scheduler::is_runnable(&self.tasks[index]) {
+                break;
+            }
+
+            let output = self.tasks[index]
+                .int_code
+                .step()
+                .map_err(|e| format!("{}: {}", self.tasks[index].name, e))?;
+
+            if let Some(o) = output {
+                self.send(index, o);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn send(&mut self, from: usize, value: i64) {
+        let name = self.tasks[from].name.clone();
+
+        for channel in self.channels.iter_mut().filter(|c| c.from == name) {
+            channel.messages.push(value);
+
+            if let Some(task) = self.tasks.iter_mut().find(|t| t.name == channel.to) {
+                task.int_code.push_input(value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+
+    static TOPOLOGY_PATH: &str = "input/amplifiers.topology";
+
+    #[test]
+    fn from_topology_feedback_loop_correct_output() {
+        let path = Path::new(TOPOLOGY_PATH);
+        let text = fs::read_to_string(path).unwrap();
+
+        let mut scheduler = Scheduler::from_topology(&text, path.parent().unwrap()).unwrap();
+
+        assert_eq!(scheduler.run(), Ok(Outcome::Halted));
+        assert_eq!(
+            scheduler.machine("e").unwrap().get_all_outputs().last(),
+            Some(&139_629_729)
+        );
+        assert_eq!(scheduler.channel("e_to_a").unwrap().messages.len(), 5);
+    }
+
+    #[test]
+    fn run_priority_same_result_as_round_robin() {
+        let path = Path::new(TOPOLOGY_PATH);
+        let text = fs::read_to_string(path).unwrap();
+        let text = text.replace("policy round-robin", "policy priority");
+
+        let mut scheduler = Scheduler::from_topology(&text, path.parent().unwrap()).unwrap();
+
+        assert_eq!(scheduler.run(), Ok(Outcome::Halted));
+        assert_eq!(
+            scheduler.machine("e").unwrap().get_all_outputs().last(),
+            Some(&139_629_729)
+        );
+    }
+
+    #[test]
+    fn run_waiting_on_each_other_deadlock() {
+        // in [9]; out [9]; halt
+        let program = vec![3, 9, 4, 9, 99, 0, 0, 0, 0, 0];
+
+        let mut scheduler = Scheduler::new(Policy::RoundRobin, 10);
+        scheduler
+            .add_machine("a", IntCode::new(program.clone(), None), 0)
+            .unwrap();
+        scheduler
+            .add_machine("b", IntCode::new(program, None), 0)
+            .unwrap();
+        scheduler.add_channel("a_to_b", "a", "b").unwrap();
+        scheduler.add_channel("b_to_a", "b", "a").unwrap();
+
+        assert_eq!(
+            scheduler.run(),
+            Ok(Outcome::Deadlock(vec!["a".to_owned(), "b".to_owned()]))
+        );
+    }
+
+    #[test]
+    fn from_topology_invalid_line_error() {
+        assert_eq!(
+            Scheduler::from_topology("slice 10\nchannel x a -> b\n", Path::new(".")),
+            Err("line 2: unknown machine: a".to_owned())
+        );
+    }
+}
@@ -0,0 +1,300 @@
+use std::borrow::Borrow;
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use crate::int_code::IntCode;
+
+/// one point of the parameter space: memory patches applied to the base
+/// program and the inputs fed to it
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct Candidate {
+    pub patches: Vec<(usize, i64)>,
+    pub inputs: Vec<i64>,
+}
+
+impl Candidate {
+    /// every combination of values for the given memory cells, in
+    /// lexicographic order
+    pub fn memory_grid(cells: &[(usize, RangeInclusive<i64>)]) -> Vec<Candidate> {
+        let ranges: Vec<RangeInclusive<i64>> = cells.iter().map(|(_, r)| r.clone()).collect();
+
+        Assignments::new(&ranges)
+            .map(|values| Candidate {
+                patches: cells.iter().map(|(a, _)| *a).zip(values).collect(),
+                inputs: Vec::new(),
+            })
+            .collect()
+    }
+
+    pub fn input_sequences(sequences: Vec<Vec<i64>>) -> Vec<Candidate> {
+        sequences
+            .into_iter()
+            .map(|inputs| Candidate {
+                patches: Vec::new(),
+                inputs,
+            })
+            .collect()
+    }
+}
+
+/// all assignments of the given ranges in lexicographic order
+pub(crate) struct Assignments {
+    ranges: Vec<RangeInclusive<i64>>,
+    next: Option<Vec<i64>>,
+}
+
+impl Assignments {
+    pub(crate) fn new(ranges: &[RangeInclusive<i64>]) -> Self {
+        let next = if ranges.iter().any(|r| r.is_empty()) {
+            None
+        } else {
+            Some(ranges.iter().map(|r| *r.start()).collect())
+        };
+
+        Assignments {
+            ranges: ranges.to_vec(),
+            next,
+        }
+    }
+}
+
+impl Iterator for Assignments {
+    type Item = Vec<i64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next.take()?;
+        let mut next = current.clone();
+
+        for i in (0..next.len()).rev() {
+            if next[i] < *self.ranges[i].end() {
+                next[i] += 1;
+                self.next = Some(next);
+                break;
+            }
+
+            next[i] = *self.ranges[i].start();
+        }
+
+        Some(current)
+    }
+}
+
+/// all orderings of `values`, e.g. for phase settings
+pub fn permutations(values: &[i64]) -> Vec<Vec<i64>> {
+    if values.len() <= 1 {
+        return vec![values.to_vec()];
+    }
+
+    (0..values.len())
+        .flat_map(|i| {
+            let mut rest = values.to_vec();
+            let first = rest.remove(i);
+
+            permutations(&rest).into_iter().map(move |mut p| {
+                p.insert(0, first);
+                p
+            })
+        })
+        .collect()
+}
+
+/// steps a candidate may take before its run fails, so a single program which
+/// never halts can't block the whole sweep
+pub const DEFAULT_MAX_STEPS: usize = 1_000_000;
+
+/// runs a base program for many candidates in parallel, candidates whose run
+/// fails (or exceeds the step limit) never match
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Sweep {
+    base: IntCode,
+    threads: usize,
+    max_steps: Option<usize>,
+}
+
+impl Sweep {
+    pub fn new(base: IntCode) -> Self {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+
+        Sweep {
+            base,
+            threads,
+            max_steps: Some(DEFAULT_MAX_STEPS),
+        }
+    }
+
+    pub fn set_threads(&mut self, threads: usize) {
+        self.threads = threads.max(1);
+    }
+
+    /// `None` runs every candidate until it halts or fails
+    pub fn set_max_steps(&mut self, max_steps: Option<usize>) {
+        self.max_steps = max_steps;
+    }
+
+    fn run_candidate(&self, candidate: &Candidate) -> Result<IntCode, String> {
+        let mut int_code = self.base.clone();
+
+        candidate
+            .patches
+            .iter()
+            .for_each(|(address, value)| int_code.set_memory(*address, *value));
+        int_code.set_inputs(&candidate.inputs);
+
+        while !int_code.is_done() {
+            if let Some(max) = self.max_steps {
+                if int_code.get_steps() >= max {
+                    return Err(format!("no halt within {} steps", max));
+                }
+            }
+
+            int_code.step()?;
+        }
+
+        Ok(int_code)
+    }
+
+    /// first candidate (in the given order) whose finished program satisfies
+    /// `predicate`, candidates are taken from the iterator as workers need
+    /// them and workers stop as soon as no earlier candidate can match
+    pub fn find_first<I, F>(&self, candidates: I, predicate: F) -> Option<(usize, IntCode)>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Candidate>,
+        I::IntoIter: Send,
+        F: Fn(&IntCode) -> bool + Sync,
+    {
+        let candidates = Mutex::new(candidates.into_iter().enumerate());
+        let best = AtomicUsize::new(usize::MAX);
+        let found: Mutex<Option<(usize, IntCode)>> = Mutex::new(None);
+
+        thread::scope(|s| {
+            for _ in 0..self.threads {
+                s.spawn(|| loop {
+                    let (i, candidate) = match candidates.lock().unwrap().next() {
+                        Some(next) => next,
+                        None => break,
+                    };
+
+                    if i > best.load(Ordering::SeqCst) {
+                        break;
+                    }
+
+                    let int_code = match self.run_candidate(candidate.borrow()) {
+                        Ok(int_code) if predicate(&int_code) => int_code,
+                        _ => continue,
+                    };
+
+                    if best.fetch_min(i, Ordering::SeqCst) > i {
+                        let mut found = found.lock().unwrap();
+
+                        if found.as_ref().is_none_or(|(j, _)| i < *j) {
+                            *found = Some((i, int_code));
+                        }
+                    }
+                });
+            }
+        });
+
+        found.into_inner().unwrap()
+    }
+
+    /// every matching candidate with its finished program, in candidate order
+    pub fn find_all<I, F>(&self, candidates: I, predicate: F) -> Vec<(usize, IntCode)>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Candidate>,
+        I::IntoIter: Send,
+        F: Fn(&IntCode) -> bool + Sync,
+    {
+        let candidates = Mutex::new(candidates.into_iter().enumerate());
+        let found: Mutex<Vec<(usize, IntCode)>> = Mutex::new(Vec::new());
+
+        thread::scope(|s| {
+            for _ in 0..self.threads {
+                s.spawn(|| loop {
+                    let (i, candidate) = match candidates.lock().unwrap().next() {
+                        Some(next) => next,
+                        None => break,
+                    };
+
+                    if let Ok(int_code) = self.run_candidate(candidate.borrow()) {
+                        if predicate(&int_code) {
+                            found.lock().unwrap().push((i, int_code));
+                        }
+                    }
+                });
+            }
+        });
+
+        let mut found = found.into_inner().unwrap();
+        found.sort_by_key(|(i, _)| *i);
+        found
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // out a * b with both factors as immediate operands at 1 and 2
+    fn product_program() -> IntCode {
+        IntCode::new(vec![1102, 0, 0, 9, 4, 9, 99, 0, 0, 0], None)
+    }
+
+    #[test]
+    fn find_first_memory_grid_correct_output() {
+        let mut sweep = Sweep::new(product_program());
+        sweep.set_threads(4);
+
+        let candidates = Candidate::memory_grid(&[(1, 0..=9), (2, 0..=9)]);
+        let (index, int_code) = sweep
+            .find_first(&candidates, |i| i.get_all_outputs() == &vec![12])
+            .unwrap();
+
+        assert_eq!(candidates[index].patches, vec![(1, 2), (2, 6)]);
+        assert_eq!(int_code.get_memory()[9], 12);
+    }
+
+    #[test]
+    fn find_all_input_sequences_correct_output() {
+        // out in - in
+        let base = IntCode::new(
+            vec![3, 20, 3, 21, 1002, 21, -1, 21, 1, 20, 21, 22, 4, 22, 99],
+            None,
+        );
+
+        let sweep = Sweep::new(base);
+        let candidates = Candidate::input_sequences(permutations(&[1, 2, 3]));
+
+        let matches: Vec<Vec<i64>> = sweep
+            .find_all(&candidates, |i| i.get_all_outputs()[0] > 0)
+            .into_iter()
+            .map(|(i, _)| candidates[i].inputs.clone())
+            .collect();
+
+        assert_eq!(matches, vec![vec![2, 1, 3], vec![3, 1, 2], vec![3, 2, 1]]);
+    }
+
+    #[test]
+    fn permutations_correct_output() {
+        assert_eq!(permutations(&[]), vec![Vec::<i64>::new()]);
+        assert_eq!(permutations(&[0, 1, 2]).len(), 6);
+    }
+
+    #[test]
+    fn find_all_non_halting_candidate_no_match() {
+        // jnz in, 2 jumps to itself forever unless the input is 0
+        let sweep = Sweep::new(IntCode::new(vec![3, 6, 1005, 6, 2, 99, 0], None));
+        let candidates = Candidate::input_sequences(vec![vec![1], vec![0], vec![2]]);
+
+        let matches: Vec<usize> = sweep
+            .find_all(&candidates, |_| true)
+            .into_iter()
+            .map(|(i, _)| i)
+            .collect();
+
+        assert_eq!(matches, vec![1]);
+    }
+}
@@ -0,0 +1,530 @@
+use std::convert::TryFrom;
+use std::ops::RangeInclusive;
+
+use crate::int_code::{IntCode, Mode, OpCode};
+use crate::sweep::{Assignments, Candidate, Sweep};
+
+/// memory cell or n-th consumed input which is treated as an unknown
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Symbol {
+    Memory(usize),
+    Input(usize),
+}
+
+/// value the solver should drive to the wanted result
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Target {
+    Memory(usize),
+    Output(usize),
+}
+
+/// `constant + sum(coefficients[i] * variable[i])`
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct LinearExpression {
+    pub constant: i64,
+    pub coefficients: Vec<i64>,
+}
+
+impl LinearExpression {
+    fn constant(value: i64, variables: usize) -> Self {
+        LinearExpression {
+            constant: value,
+            coefficients: vec![0; variables],
+        }
+    }
+
+    fn variable(index: usize, variables: usize) -> Self {
+        let mut expression = LinearExpression::constant(0, variables);
+        expression.coefficients[index] = 1;
+        expression
+    }
+
+    fn as_constant(&self) -> Option<i64> {
+        if self.coefficients.iter().all(|c| *c == 0) {
+            Some(self.constant)
+        } else {
+            None
+        }
+    }
+
+    fn add(&self, other: &LinearExpression) -> Option<LinearExpression> {
+        let coefficients = self
+            .coefficients
+            .iter()
+            .zip(other.coefficients.iter())
+            .map(|(a, b)| a.checked_add(*b))
+            .collect::<Option<Vec<i64>>>()?;
+
+        Some(LinearExpression {
+            constant: self.constant.checked_add(other.constant)?,
+            coefficients,
+        })
+    }
+
+    fn scale(&self, factor: i64) -> Option<LinearExpression> {
+        let coefficients = self
+            .coefficients
+            .iter()
+            .map(|c| c.checked_mul(factor))
+            .collect::<Option<Vec<i64>>>()?;
+
+        Some(LinearExpression {
+            constant: self.constant.checked_mul(factor)?,
+            coefficients,
+        })
+    }
+
+    fn mul(&self, other: &LinearExpression) -> Option<LinearExpression> {
+        match (self.as_constant(), other.as_constant()) {
+            (Some(c), _) => other.scale(c),
+            (_, Some(c)) => self.scale(c),
+            _ => None,
+        }
+    }
+
+    pub fn evaluate(&self, assignment: &[i64]) -> Option<i64> {
+        self.coefficients
+            .iter()
+            .zip(assignment.iter())
+            .try_fold(self.constant, |acc, (c, v)| {
+                acc.checked_add(c.checked_mul(*v)?)
+            })
+    }
+}
+
+// a cell holding a non-linear function of the variables is only a problem
+// once it influences control flow, addressing or the target
+#[derive(PartialEq, Eq, Debug, Clone)]
+enum Value {
+    Linear(LinearExpression),
+    Unknown,
+}
+
+impl Value {
+    fn concrete(&self) -> Option<i64> {
+        match self {
+            Value::Linear(e) => e.as_constant(),
+            Value::Unknown => None,
+        }
+    }
+}
+
+static MAX_STEPS: usize = 1_000_000;
+
+/// solves for the values of selected memory cells / inputs which make a
+/// program produce a wanted result, symbolically if every operation on the
+/// unknowns stays linear and by trying all candidates otherwise
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Solver {
+    memory: Vec<i64>,
+    variables: Vec<(Symbol, RangeInclusive<i64>)>,
+}
+
+impl Solver {
+    pub fn new(memory: Vec<i64>) -> Self {
+        Solver {
+            memory,
+            variables: Vec::new(),
+        }
+    }
+
+    pub fn add_variable(&mut self, symbol: Symbol, range: RangeInclusive<i64>) {
+        self.variables.push((symbol, range));
+    }
+
+    /// first assignment (in lexicographic order of the variables) for which
+    /// `target` equals `wanted`
+    pub fn solve(&self, target: Target, wanted: i64) -> Result<Option<Vec<i64>>, String> {
+        match self.symbolic_target(target) {
+            Ok(expression) => Ok(self.solve_linear(&expression, wanted)),
+            Err(_) => self.brute_force(target, wanted),
+        }
+    }
+
+    /// executes the program with the variables as unknowns and returns the
+    /// target as linear expression of them
+    pub fn symbolic_target(&self, target: Target) -> Result<LinearExpression, String> {
+        let n = self.variables.len();
+
+        let mut memory: Vec<Value> = self
+            .memory
+            .iter()
+            .map(|v| Value::Linear(LinearExpression::constant(*v, n)))
+            .collect();
+        let mut inputs: Vec<Value> = Vec::new();
+
+        for (i, (symbol, _)) in self.variables.iter().enumerate() {
+            let value = Value::Linear(LinearExpression::variable(i, n));
+
+            match symbol {
+                Symbol::Memory(address) => {
+                    if *address >= memory.len() {
+                        memory.resize(address + 1, Value::Linear(LinearExpression::constant(0, n)));
+                    }
+                    memory[*address] = value;
+                }
+                Symbol::Input(index) => {
+                    if *index >= inputs.len() {
+                        inputs.resize(index + 1, Value::Unknown);
+                    }
+                    inputs[*index] = value;
+                }
+            }
+        }
+
+        let mut execution = Execution {
+            memory,
+            inputs,
+            outputs: Vec::new(),
+            ip: 0,
+            rb: 0,
+            variables: n,
+        };
+
+        execution.run()?;
+
+        let value = match target {
+            Target::Memory(address) => execution.memory.get(address).cloned(),
+            Target::Output(index) => execution.outputs.get(index).cloned(),
+        };
+
+        match value {
+            Some(Value::Linear(expression)) => Ok(expression),
+            Some(Value::Unknown) => Err("target is not linear".to_owned()),
+            None => Err("target was not produced".to_owned()),
+        }
+    }
+
+    fn solve_linear(&self, expression: &LinearExpression, wanted: i64) -> Option<Vec<i64>> {
+        let starts: Vec<i64> = self.variables.iter().map(|(_, r)| *r.start()).collect();
+
+        let free: Vec<usize> = (0..self.variables.len())
+            .filter(|i| expression.coefficients[*i] != 0)
+            .collect();
+
+        let last = match free.last() {
+            Some(l) => *l,
+            None => {
+                return if expression.constant == wanted {
+                    Some(starts)
+                } else {
+                    None
+                };
+            }
+        };
+
+        let enumerated: Vec<usize> = free[..free.len() - 1].to_vec();
+        let ranges: Vec<RangeInclusive<i64>> = enumerated
+            .iter()
+            .map(|i| self.variables[*i].1.clone())
+            .collect();
+
+        for values in Assignments::new(&ranges) {
+            let mut assignment = starts.clone();
+            enumerated
+                .iter()
+                .zip(values.iter())
+                .for_each(|(i, v)| assignment[*i] = *v);
+            assignment[last] = 0;
+
+            let rest = match expression
+                .evaluate(&assignment)
+                .and_then(|e| wanted.checked_sub(e))
+            {
+                Some(r) => r,
+                None => continue,
+            };
+
+            let coefficient = expression.coefficients[last];
+
+            if rest % coefficient != 0 {
+                continue;
+            }
+
+            let value = rest / coefficient;
+
+            if self.variables[last].1.contains(&value) {
+                assignment[last] = value;
+                return Some(assignment);
+            }
+        }
+
+        None
+    }
+
+    /// runs the program once for every candidate assignment, runs which fail
+    /// or don't halt within the step limit count as no match
+    pub fn brute_force(&self, target: Target, wanted: i64) -> Result<Option<Vec<i64>>, String> {
+        let ranges: Vec<RangeInclusive<i64>> =
+            self.variables.iter().map(|(_, r)| r.clone()).collect();
+
+        let candidates = Assignments::new(&ranges).map(|assignment| {
+            let mut candidate = Candidate::default();
+            let mut inputs: Vec<(usize, i64)> = Vec::new();
+
+            for ((symbol, _), value) in self.variables.iter().zip(assignment.iter()) {
+                match symbol {
+                    Symbol::Memory(address) => candidate.patches.push((*address, *value)),
+                    Symbol::Input(index) => inputs.push((*index, *value)),
+                }
+            }
+
+            inputs.sort();
+            candidate.inputs = inputs.into_iter().map(|(_, v)| v).collect();
+            candidate
+        });
+
+        let mut sweep = Sweep::new(IntCode::new(self.memory.clone(), None));
+        sweep.set_max_steps(Some(MAX_STEPS));
+
+        let found = sweep.find_first(candidates, |int_code| {
+            let value = match target {
+                Target::Memory(address) => int_code.get_memory().get(address),
+                Target::Output(index) => int_code.get_all_outputs().get(index),
+            };
+
+            value == Some(&wanted)
+        });
+
+        Ok(found.and_then(|(i, _)| Assignments::new(&ranges).nth(i)))
+    }
+}
+
+struct Execution {
+    memory: Vec<Value>,
+    inputs: Vec<Value>,
+    outputs: Vec<Value>,
+    ip: usize,
+    rb: isize,
+    variables: usize,
+}
+
+impl Execution {
+    fn run(&mut self) -> Result<(), String> {
+        let mut consumed_inputs = 0;
+
+        for _ in 0..MAX_STEPS {
+            let op_code = OpCode::decode(self.concrete(self.ip)?)
+                .ok_or_else(|| format!("invalid op code at {}", self.ip))?;
+
+            let next_ip = self.ip + 1 + op_code.parameter_count();
+
+            match op_code {
+                OpCode::End => return Ok(()),
+                OpCode::Add {
+                    p1_mode,
+                    p2_mode,
+                    p3_mode,
+                } => {
+                    let value = match (self.read(p1_mode, 1)?, self.read(p2_mode, 2)?) {
+                        (Value::Linear(a), Value::Linear(b)) => {
+                            Value::Linear(a.add(&b).ok_or_else(|| "overflow".to_owned())?)
+                        }
+                        _ => Value::Unknown,
+                    };
+                    self.write(p3_mode, 3, value)?;
+                }
+                OpCode::Mut {
+                    p1_mode,
+                    p2_mode,
+                    p3_mode,
+                } => {
+                    let value = match (self.read(p1_mode, 1)?, self.read(p2_mode, 2)?) {
+                        (Value::Linear(a), Value::Linear(b)) => {
+                            a.mul(&b).map_or(Value::Unknown, Value::Linear)
+                        }
+                        _ => Value::Unknown,
+                    };
+                    self.write(p3_mode, 3, value)?;
+                }
+                OpCode::LessThan {
+                    p1_mode,
+                    p2_mode,
+                    p3_mode,
+                }
+                | OpCode::Equals {
+                    p1_mode,
+                    p2_mode,
+                    p3_mode,
+                } => {
+                    let a = self.read_concrete(p1_mode, 1)?;
+                    let b = self.read_concrete(p2_mode, 2)?;
+                    let result = match op_code {
+                        OpCode::LessThan { .. } => a < b,
+                        _ => a == b,
+                    };
+                    let value =
+                        Value::Linear(LinearExpression::constant(result as i64, self.variables));
+                    self.write(p3_mode, 3, value)?;
+                }
+                OpCode::Input { p1_mode } => {
+                    let value = self
+                        .inputs
+                        .get(consumed_inputs)
+                        .cloned()
+                        .filter(|v| *v != Value::Unknown)
+                        .ok_or_else(|| format!("input {} is not a variable", consumed_inputs))?;
+                    consumed_inputs += 1;
+                    self.write(p1_mode, 1, value)?;
+                }
+                OpCode::Output { p1_mode } => {
+                    let value = self.read(p1_mode, 1)?;
+                    self.outputs.push(value);
+                }
+                OpCode::JumpIfTrue { p1_mode, p2_mode }
+                | OpCode::JumpIfFalse { p1_mode, p2_mode } => {
+                    let condition = self.read_concrete(p1_mode, 1)?;
+                    let jump = match op_code {
+                        OpCode::JumpIfTrue { .. } => condition != 0,
+                        _ => condition == 0,
+                    };
+
+                    if jump {
+                        let target = self.read_concrete(p2_mode, 2)?;
+                        self.ip = usize::try_from(target).map_err(|e| e.to_string())?;
+                        continue;
+                    }
+                }
+                OpCode::AdjustRelativeBase { p1_mode } => {
+                    self.rb += self.read_concrete(p1_mode, 1)? as isize;
+                }
+                OpCode::Unknown => return Err("Unknown opcode".to_owned()),
+            }
+
+            self.ip = next_ip;
+        }
+
+        Err(format!("no halt within {} steps", MAX_STEPS))
+    }
+
+    fn concrete(&self, address: usize) -> Result<i64, String> {
+        self.memory
+            .get(address)
+            .map_or(Some(0), |v| v.concrete())
+            .ok_or_else(|| format!("symbolic value at {}", address))
+    }
+
+    fn address(&self, mode: Mode, offset: usize) -> Result<Option<usize>, String> {
+        let pos = self.ip + offset;
+
+        let address = match mode {
+            Mode::Immediate => return Ok(Some(pos)),
+            Mode::Position => match self.memory.get(pos).and_then(|v| v.concrete()) {
+                Some(a) => a,
+                None => return Ok(None),
+            },
+            Mode::Relative => match self.memory.get(pos).and_then(|v| v.concrete()) {
+                Some(a) => self.rb as i64 + a,
+                None => return Ok(None),
+            },
+        };
+
+        usize::try_from(address)
+            .map(Some)
+            .map_err(|e| e.to_string())
+    }
+
+    fn read(&self, mode: Mode, offset: usize) -> Result<Value, String> {
+        let value =
+            match self.address(mode, offset)? {
+                Some(address) => self.memory.get(address).cloned().unwrap_or_else(|| {
+                    Value::Linear(LinearExpression::constant(0, self.variables))
+                }),
+                None => Value::Unknown,
+            };
+
+        Ok(value)
+    }
+
+    fn read_concrete(&self, mode: Mode, offset: usize) -> Result<i64, String> {
+        self.read(mode, offset)?
+            .concrete()
+            .ok_or_else(|| format!("symbolic operand at {}", self.ip))
+    }
+
+    fn write(&mut self, mode: Mode, offset: usize, value: Value) -> Result<(), String> {
+        let address = self
+            .address(mode, offset)?
+            .ok_or_else(|| format!("write to symbolic address at {}", self.ip))?;
+
+        if address >= self.memory.len() {
+            self.memory.resize(
+                address + 1,
+                Value::Linear(LinearExpression::constant(0, self.variables)),
+            );
+        }
+
+        self.memory[address] = value;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // like day 2 the first instruction uses noun and verb as pointers, its
+    // result is overwritten by [0] = [1] * 3 + [2] + 7
+    fn linear_program() -> Vec<i64> {
+        vec![
+            1, 0, 0, 17, 1002, 1, 3, 17, 1, 17, 2, 17, 1001, 17, 7, 0, 99, 0,
+        ]
+    }
+
+    fn noun_verb_solver(memory: Vec<i64>) -> Solver {
+        let mut solver = Solver::new(memory);
+        solver.add_variable(Symbol::Memory(1), 0..=99);
+        solver.add_variable(Symbol::Memory(2), 0..=99);
+        solver
+    }
+
+    #[test]
+    fn symbolic_target_linear_correct_output() {
+        let solver = noun_verb_solver(linear_program());
+
+        assert_eq!(
+            solver.symbolic_target(Target::Memory(0)),
+            Ok(LinearExpression {
+                constant: 7,
+                coefficients: vec![3, 1]
+            })
+        );
+    }
+
+    #[test]
+    fn solve_linear_same_as_brute_force() {
+        let solver = noun_verb_solver(linear_program());
+
+        let solved = solver.solve(Target::Memory(0), 250).unwrap();
+        assert_eq!(solved, Some(vec![48, 99]));
+        assert_eq!(solved, solver.brute_force(Target::Memory(0), 250).unwrap());
+
+        assert_eq!(solver.solve(Target::Memory(0), 1000).unwrap(), None);
+    }
+
+    #[test]
+    fn solve_non_linear_falls_back_to_brute_force() {
+        // out = in * in
+        let mut solver = Solver::new(vec![3, 9, 2, 9, 9, 10, 4, 10, 99, 0, 0]);
+        solver.add_variable(Symbol::Input(0), -20..=20);
+
+        assert!(solver.symbolic_target(Target::Output(0)).is_err());
+        assert_eq!(
+            solver.solve(Target::Output(0), 144).unwrap(),
+            Some(vec![-12])
+        );
+    }
+
+    #[test]
+    fn brute_force_non_halting_candidate_no_match() {
+        // jnz v, 0 loops forever unless v is 0, [0] = 2 + 3
+        let mut solver = Solver::new(vec![1105, 0, 0, 1101, 2, 3, 0, 99]);
+        solver.add_variable(Symbol::Memory(1), -1..=0);
+
+        assert_eq!(
+            solver.brute_force(Target::Memory(0), 5).unwrap(),
+            Some(vec![0])
+        );
+    }
+}